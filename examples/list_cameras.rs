@@ -0,0 +1,21 @@
+extern crate gphoto;
+
+fn main() {
+    let mut context = match gphoto::Context::new() {
+        Ok(c) => c,
+        Err(err) => panic!("error creating context: {}", err)
+    };
+
+    let cameras = match gphoto::Camera::detect_all(&mut context) {
+        Ok(c) => c,
+        Err(err) => panic!("error detecting cameras: {}", err)
+    };
+
+    if cameras.is_empty() {
+        println!("no cameras detected");
+    }
+
+    for camera in cameras {
+        println!("{:<32} {}", camera.model(), camera.port_path());
+    }
+}
@@ -0,0 +1,45 @@
+extern crate gphoto;
+
+fn print_widget(widget: &gphoto::Widget, path: &str) {
+    let path = format!("{}/{}", path, widget.name());
+
+    match widget.value() {
+        Some(value) => {
+            println!("{}", path);
+            println!("    label = {:?}", widget.label());
+            println!("     type = {:?}", widget.widget_type());
+            println!(" readonly = {:?}", widget.readonly());
+            println!("    value = {:?}", value);
+
+            if let Some(range) = widget.range() {
+                println!("    range = {:?}", range);
+            }
+
+            for choice in widget.choices() {
+                println!("   choice = {:?}", choice);
+            }
+        },
+        None => {
+            for child in widget.children() {
+                print_widget(&child, &path);
+            }
+        },
+    }
+}
+
+fn main() {
+    let mut context = match gphoto::Context::new() {
+        Ok(c) => c,
+        Err(err) => panic!("error creating context: {}", err)
+    };
+
+    let mut camera = match gphoto::Camera::autodetect(&mut context) {
+        Ok(c) => c,
+        Err(err) => panic!("error opening camera: {}", err)
+    };
+
+    match camera.config(&mut context) {
+        Ok(config) => print_widget(&config, ""),
+        Err(err) => panic!("error retrieving configuration: {}", err)
+    }
+}
@@ -1,9 +1,15 @@
 use std::borrow::Cow;
 use std::collections::HashSet;
-use std::ffi::CStr;
+use std::ffi::{CStr,CString};
+use std::mem;
 
+use ::libc::c_int;
+
+use ::context::Context;
 use ::port::{PortType};
 
+use ::handle::prelude::*;
+
 /// Describes the abilities of a device.
 ///
 /// ## Example
@@ -220,6 +226,157 @@ impl Abilities {
     }
 }
 
+#[doc(hidden)]
+impl Handle<::gphoto2::CameraAbilities> for Abilities {
+    unsafe fn as_ptr(&self) -> *const ::gphoto2::CameraAbilities {
+        &self.inner
+    }
+}
+
+/// The database of camera models supported by the installed camera drivers.
+///
+/// ## Example
+///
+/// An `AbilitiesList` can be used to check whether a camera model is supported without having the
+/// camera attached:
+///
+/// ```no_run
+/// let mut context = gphoto::Context::new().unwrap();
+/// let list = gphoto::AbilitiesList::new(&mut context).unwrap();
+///
+/// println!("{} supported models", list.len());
+///
+/// if let Some(abilities) = list.find_model("Nikon DSC D750") {
+///     println!("{:?}", abilities.driver_status());
+/// }
+///
+/// if let Some(abilities) = list.find_usb(0x04b0, 0x0437) {
+///     println!("{}", abilities.model());
+/// }
+/// ```
+pub struct AbilitiesList {
+    list: *mut ::gphoto2::CameraAbilitiesList,
+}
+
+impl Drop for AbilitiesList {
+    fn drop(&mut self) {
+        unsafe {
+            ::gphoto2::gp_abilities_list_free(self.list);
+        }
+    }
+}
+
+impl AbilitiesList {
+    /// Loads the abilities of every camera model supported by the installed camera drivers.
+    pub fn new(context: &mut Context) -> ::Result<Self> {
+        let mut list = mem::MaybeUninit::uninit();
+
+        try_unsafe!(::gphoto2::gp_abilities_list_new(list.as_mut_ptr()));
+
+        let list = AbilitiesList { list: unsafe { list.assume_init() } };
+
+        try_unsafe!(::gphoto2::gp_abilities_list_load(list.list, context.as_mut_ptr()));
+
+        Ok(list)
+    }
+
+    /// Returns the number of camera models in the list.
+    pub fn len(&self) -> usize {
+        match unsafe { ::gphoto2::gp_abilities_list_count(self.list) } {
+            n if n > 0 => n as usize,
+            _ => 0,
+        }
+    }
+
+    /// Returns `true` if the list contains no camera models.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the abilities of the camera model at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Abilities> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let mut abilities = mem::MaybeUninit::uninit();
+
+        match unsafe { ::gphoto2::gp_abilities_list_get_abilities(self.list, index as c_int, abilities.as_mut_ptr()) } {
+            ::gphoto2::GP_OK => Some(from_libgphoto2(unsafe { abilities.assume_init() })),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the abilities of every camera model in the list.
+    pub fn iter(&self) -> AbilitiesIter<'_> {
+        AbilitiesIter {
+            list: self,
+            index: 0,
+        }
+    }
+
+    /// Looks up the abilities of a camera model by name.
+    ///
+    /// The model name is matched case-insensitively. Returns `None` if no driver supports the
+    /// model.
+    pub fn find_model(&self, model: &str) -> Option<Abilities> {
+        let model = match CString::new(model) {
+            Ok(s) => s,
+            Err(_) => return None,
+        };
+
+        match unsafe { ::gphoto2::gp_abilities_list_lookup_model(self.list, model.as_ptr()) } {
+            index if index >= 0 => self.get(index as usize),
+            _ => None,
+        }
+    }
+
+    /// Looks up the abilities of a camera model by its USB vendor and product IDs.
+    ///
+    /// Returns the first model that matches both IDs, or `None` if no driver claims the device.
+    pub fn find_usb(&self, vendor: u16, product: u16) -> Option<Abilities> {
+        self.iter().find(|abilities| {
+            abilities.usb_vendor() == vendor && abilities.usb_product() == product
+        })
+    }
+}
+
+impl<'a> IntoIterator for &'a AbilitiesList {
+    type Item = Abilities;
+    type IntoIter = AbilitiesIter<'a>;
+
+    fn into_iter(self) -> AbilitiesIter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the camera models in an `AbilitiesList`.
+pub struct AbilitiesIter<'a> {
+    list: &'a AbilitiesList,
+    index: usize,
+}
+
+impl<'a> Iterator for AbilitiesIter<'a> {
+    type Item = Abilities;
+
+    fn next(&mut self) -> Option<Abilities> {
+        let abilities = self.list.get(self.index);
+
+        if abilities.is_some() {
+            self.index += 1;
+        }
+
+        abilities
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.list.len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
 /// Types of devices.
 #[derive(Debug,PartialEq,Eq,Clone,Copy,Hash)]
 pub enum DeviceType {
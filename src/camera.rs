@@ -1,18 +1,49 @@
 use std::borrow::Cow;
-use std::ffi::CStr;
+use std::collections::BTreeMap;
+use std::ffi::{CStr,CString};
+use std::fmt;
 use std::mem;
+use std::path::Path;
+use std::ptr;
+use std::thread;
+use std::time::{Duration,SystemTime};
 
-use ::context::Context;
-use ::abilities::Abilities;
+use ::libc::{c_char,c_int,c_void};
+
+use ::context::{Context,Handler};
+use ::error::ErrorKind;
+use ::abilities::{Abilities,AbilitiesList};
+use ::list::CameraList;
 use ::media::Media;
-use ::port::Port;
+use ::port::{Port,PortInfoList,PortType};
+use ::preset::Preset;
+use ::snapshot::{ConfigSnapshot,ApplyOutcome};
 use ::storage::Storage;
+use ::widget::{Widget,WidgetType,SettingValue};
 
 use ::handle::prelude::*;
 
+/// Model name of the generic PTP/IP camera driver.
+const PTPIP_MODEL: &str = "PTP/IP Camera";
+
+/// Model name of the driver that browses a local directory.
+const DIRECTORY_MODEL: &str = "Directory Browse";
+
+/// Number of times `Camera::with_recovery()` attempts an operation.
+const RECOVERY_ATTEMPTS: usize = 3;
+
+/// Time to wait before retrying an operation that failed with a recoverable error.
+const RECOVERY_DELAY: Duration = Duration::from_millis(500);
+
+/// Names of the date widgets that hold the camera's clock, in order of preference.
+const CLOCK_SETTINGS: [&str; 2] = ["datetimeutc", "datetime"];
+
 /// A structure representing a camera connected to the system.
 pub struct Camera {
     camera: *mut ::gphoto2::Camera,
+
+    // The serial number of a USB camera, used to recognize the camera on a new port path.
+    serial_number: Option<String>,
 }
 
 impl Drop for Camera {
@@ -23,18 +54,308 @@ impl Drop for Camera {
     }
 }
 
+#[doc(hidden)]
+impl Handle<::gphoto2::Camera> for Camera {
+    unsafe fn as_ptr(&self) -> *const ::gphoto2::Camera {
+        self.camera
+    }
+}
+
+#[doc(hidden)]
+impl HandleMut<::gphoto2::Camera> for Camera {
+    unsafe fn as_mut_ptr(&mut self) -> *mut ::gphoto2::Camera {
+        self.camera
+    }
+}
+
 impl Camera {
     /// Opens the first detected camera.
     pub fn autodetect(context: &mut Context) -> ::Result<Self> {
+        let mut camera = Camera::new()?;
+
+        try_unsafe!(::gphoto2::gp_camera_init(camera.camera, context.as_mut_ptr()));
+
+        camera.remember_serial_number(context);
+
+        Ok(camera)
+    }
+
+    /// Opens a specific camera given its model name and port path.
+    ///
+    /// The model is resolved through the database of supported cameras and the port through the
+    /// list of ports available on the system. The values returned by `Camera::detect_all()` can be
+    /// used to open a particular camera when several are connected.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let camera = gphoto::Camera::open(&mut context, "Nikon DSC D750", "usb:001,007").unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera could not be opened:
+    ///
+    /// * `ModelNotFound` if no camera driver supports the model.
+    /// * `UnknownPort` if the port path does not match any port on the system.
+    pub fn open(context: &mut Context, model: &str, port_path: &str) -> ::Result<Self> {
+        CameraBuilder::new().model(model).port(port_path).build(context)
+    }
+
+    /// Opens a camera connected over a network with PTP/IP.
+    ///
+    /// The address is the camera's host name or IP address, optionally followed by a TCP port, e.g.,
+    /// `"192.168.1.20"` or `"127.0.0.1:15740"`. A `"ptpip:"` prefix is accepted but not required.
+    ///
+    /// Cameras usually pair with a specific client GUID. If `guid` is given, it is stored in the
+    /// `libgphoto2` settings as the GUID to present to cameras; otherwise the GUID from a previous
+    /// connection is used, or a new one is generated. See `CameraBuilder::ptpip_guid()` for the GUID
+    /// format.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let camera = gphoto::Camera::open_ptpip(&mut context, "192.168.1.20", None).unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera could not be opened:
+    ///
+    /// * `InvalidInput` if the GUID is malformed.
+    /// * `IO` if the camera could not be reached.
+    pub fn open_ptpip(context: &mut Context, address: &str, guid: Option<&str>) -> ::Result<Self> {
+        let port = if address.starts_with("ptpip:") {
+            address.to_owned()
+        }
+        else {
+            format!("ptpip:{}", address)
+        };
+
+        let mut builder = CameraBuilder::new().model(PTPIP_MODEL).port(&port);
+
+        if let Some(guid) = guid {
+            builder = builder.ptpip_guid(guid);
+        }
+
+        builder.build(context)
+    }
+
+    /// Opens a local directory as if it were a camera.
+    ///
+    /// The directory is accessed through `libgphoto2`'s directory driver, which exposes the files
+    /// and subdirectories of `path` as the camera's storage. This can be used to run the same code
+    /// against a mounted memory card or a directory of test images as against a real camera.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    ///
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let camera = gphoto::Camera::open_directory(&mut context, Path::new("/media/card")).unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the directory could not be opened:
+    ///
+    /// * `DirectoryNotFound` if `path` is not an existing directory.
+    /// * `InvalidInput` if `path` is not valid UTF-8.
+    pub fn open_directory(context: &mut Context, path: &Path) -> ::Result<Self> {
+        let path = match path.canonicalize() {
+            Ok(p) if p.is_dir() => p,
+            _ => return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_DIRECTORY_NOT_FOUND)),
+        };
+
+        let port = match path.to_str() {
+            Some(p) => format!("disk:{}", p),
+            None => return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS)),
+        };
+
+        CameraBuilder::new().model(DIRECTORY_MODEL).port(&port).build(context)
+    }
+
+    fn new() -> ::Result<Self> {
         let mut camera = mem::MaybeUninit::uninit();
 
         try_unsafe!(::gphoto2::gp_camera_new(camera.as_mut_ptr()));
 
-        let camera = Camera { camera: unsafe { camera.assume_init() } };
+        Ok(Camera {
+            camera: unsafe { camera.assume_init() },
+            serial_number: None,
+        })
+    }
 
-        try_unsafe!(::gphoto2::gp_camera_init(camera.camera, context.as_mut_ptr()));
+    fn remember_serial_number(&mut self, context: &mut Context) {
+        if self.port().port_type() == PortType::USB {
+            self.serial_number = self.read_serial_number(context);
+        }
+    }
 
-        Ok(camera)
+    fn read_serial_number(&mut self, context: &mut Context) -> Option<String> {
+        match self.get_setting(context, "serialnumber") {
+            Ok(SettingValue::Text(serial_number)) if !serial_number.is_empty() => Some(serial_number),
+            _ => None,
+        }
+    }
+
+    fn set_port_path(&mut self, path: &str) -> ::Result<()> {
+        let ports = PortInfoList::new()?;
+
+        let port = match ports.find_path(path) {
+            Some(p) => p,
+            None => return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_UNKNOWN_PORT)),
+        };
+
+        try_unsafe!(::gphoto2::gp_camera_set_port_info(self.camera, port.as_ptr() as *mut _));
+
+        Ok(())
+    }
+
+    /// Closes the connection to the camera.
+    ///
+    /// Closing the connection releases the device so that it can be used by other processes. The
+    /// camera keeps its model and port, so the connection can be re-established with
+    /// `reconnect()`. Any other operation on the camera also re-establishes the connection.
+    pub fn exit(&mut self, context: &mut Context) -> ::Result<()> {
+        try_unsafe!(::gphoto2::gp_camera_exit(self.camera, context.as_mut_ptr()));
+
+        Ok(())
+    }
+
+    /// Closes and re-establishes the connection to the camera.
+    ///
+    /// The camera is reconnected with the same model and port. USB devices are assigned a new port
+    /// path when they are unplugged and plugged back in, so if the camera can no longer be found on
+    /// its USB port, it is reconnected on the port where a camera of the same model is detected.
+    /// This only happens if exactly one such camera is detected on another port and, if the
+    /// camera's serial number was known, the camera on the new port reports the same serial number,
+    /// so that a handle is never moved to a different camera of the same model.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the connection could not be re-established, e.g., `IO` if
+    /// the camera is still disconnected.
+    pub fn reconnect(&mut self, context: &mut Context) -> ::Result<()> {
+        // The connection may already be broken, in which case exiting fails but still releases it.
+        let _ = self.exit(context);
+
+        let err = match unsafe { ::gphoto2::gp_camera_init(self.camera, context.as_mut_ptr()) } {
+            ::gphoto2::GP_OK => return Ok(()),
+            err => ::error::from_libgphoto2(err),
+        };
+
+        match err.kind() {
+            ErrorKind::IO | ErrorKind::UnknownPort if self.port().port_type() == PortType::USB => (),
+            _ => return Err(err),
+        }
+
+        let model = self.abilities().model().into_owned();
+        let old_path = self.port().path().into_owned();
+
+        let mut detected = Camera::detect_all(context)?.into_iter().filter(|c| {
+            c.model().eq_ignore_ascii_case(&model) && c.port_path() != old_path
+        });
+
+        // Cameras of the same model can not be told apart by their ports, so a camera is only
+        // picked if it is the only candidate.
+        match (detected.next(), detected.next()) {
+            (Some(camera), None) => self.set_port_path(camera.port_path())?,
+            _ => return Err(err),
+        }
+
+        try_unsafe!(::gphoto2::gp_camera_init(self.camera, context.as_mut_ptr()));
+
+        if let Some(serial_number) = self.serial_number.clone() {
+            if self.read_serial_number(context) != Some(serial_number) {
+                let _ = self.exit(context);
+                self.set_port_path(&old_path)?;
+
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Performs an operation, recovering from transient communication errors.
+    ///
+    /// If the operation fails because the camera is busy, it is retried after a short delay. If
+    /// it fails with an I/O error, the camera is reconnected with `reconnect()` before retrying.
+    /// The operation is attempted at most three times. Any other error is returned immediately.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// let capture = camera.with_recovery(&mut context, |camera, context| {
+    ///     camera.capture_image(context)
+    /// }).unwrap();
+    /// ```
+    pub fn with_recovery<T, F>(&mut self, context: &mut Context, mut operation: F) -> ::Result<T>
+        where F: FnMut(&mut Camera, &mut Context) -> ::Result<T>
+    {
+        let mut attempt = 1;
+
+        loop {
+            let err = match operation(self, context) {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+
+            if attempt >= RECOVERY_ATTEMPTS {
+                return Err(err);
+            }
+
+            match err.kind() {
+                ErrorKind::CameraBusy => thread::sleep(RECOVERY_DELAY),
+                ErrorKind::IO => {
+                    thread::sleep(RECOVERY_DELAY);
+
+                    // A failed reconnect is retried on the next attempt.
+                    if let Err(reconnect_err) = self.reconnect(context) {
+                        if attempt + 1 >= RECOVERY_ATTEMPTS {
+                            return Err(reconnect_err);
+                        }
+                    }
+                },
+                _ => return Err(err),
+            }
+
+            attempt += 1;
+        }
+    }
+
+    /// Detects all cameras connected to the system.
+    ///
+    /// Returns a `Vec` containing one `DetectedCamera` for each camera that was found. The model
+    /// name and port path of each entry can be used to choose which camera to open.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    ///
+    /// for camera in gphoto::Camera::detect_all(&mut context).unwrap() {
+    ///     println!("{} on {}", camera.model(), camera.port_path());
+    /// }
+    /// ```
+    pub fn detect_all(context: &mut Context) -> ::Result<Vec<DetectedCamera>> {
+        let mut list = CameraList::new()?;
+
+        try_unsafe!(::gphoto2::gp_camera_autodetect(list.as_mut_ptr(), context.as_mut_ptr()));
+
+        Ok((0..list.count()).map(|i| {
+            DetectedCamera {
+                model: list.name(i),
+                port_path: list.value(i),
+            }
+        }).collect())
     }
 
     /// Captures an image.
@@ -116,6 +437,510 @@ impl Camera {
         Ok(unsafe { Vec::from_raw_parts(storage, length, length) })
     }
 
+    /// Lists the names of the folders in a folder on the camera's storage.
+    ///
+    /// `folder` is an absolute path, e.g., `"/"` or `"/store_00010001/DCIM"`.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// fn print_folder(camera: &mut gphoto::Camera, context: &mut gphoto::Context, folder: &str) {
+    ///     for file in camera.list_files(context, folder).unwrap() {
+    ///         println!("{}/{}", file.directory(), file.basename());
+    ///     }
+    ///
+    ///     for name in camera.list_folders(context, folder).unwrap() {
+    ///         print_folder(camera, context, &format!("{}/{}", folder.trim_end_matches('/'), name));
+    ///     }
+    /// }
+    ///
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// print_folder(&mut camera, &mut context, "/");
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the folder could not be listed:
+    ///
+    /// * `DirectoryNotFound` if the folder does not exist.
+    /// * `PathNotAbsolute` if `folder` is not an absolute path.
+    /// * `InvalidInput` if `folder` contains a nul byte.
+    pub fn list_folders(&mut self, context: &mut Context, folder: &str) -> ::Result<Vec<String>> {
+        let list = self.list_folder(context, folder, ::gphoto2::gp_camera_folder_list_folders)?;
+
+        Ok((0..list.count()).map(|i| list.name(i)).collect())
+    }
+
+    /// Lists the files in a folder on the camera's storage.
+    ///
+    /// `folder` is an absolute path, e.g., `"/store_00010001/DCIM/100CANON"`. The returned files can
+    /// be downloaded with `download()`.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the folder could not be listed:
+    ///
+    /// * `DirectoryNotFound` if the folder does not exist.
+    /// * `PathNotAbsolute` if `folder` is not an absolute path.
+    /// * `InvalidInput` if `folder` contains a nul byte or is too long.
+    pub fn list_files(&mut self, context: &mut Context, folder: &str) -> ::Result<Vec<CameraFile>> {
+        let list = self.list_folder(context, folder, ::gphoto2::gp_camera_folder_list_files)?;
+
+        (0..list.count()).map(|i| CameraFile::new(folder, &list.name(i))).collect()
+    }
+
+    fn list_folder(&mut self,
+                   context: &mut Context,
+                   folder: &str,
+                   list_fn: unsafe extern "C" fn(*mut ::gphoto2::Camera, *const c_char, *mut ::gphoto2::CameraList, *mut ::gphoto2::GPContext) -> c_int)
+                   -> ::Result<CameraList>
+    {
+        let folder = match CString::new(folder) {
+            Ok(s) => s,
+            Err(_) => return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS)),
+        };
+
+        let mut list = CameraList::new()?;
+
+        try_unsafe!(list_fn(self.camera, folder.as_ptr(), list.as_mut_ptr(), context.as_mut_ptr()));
+
+        Ok(list)
+    }
+
+    /// Retrieves the camera's configuration.
+    ///
+    /// Returns the root `Window` widget of the camera's configuration tree. See `Widget` for an
+    /// example.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the configuration could not be retrieved:
+    ///
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn config(&mut self, context: &mut Context) -> ::Result<Widget> {
+        let mut root = mem::MaybeUninit::uninit();
+
+        try_unsafe!(::gphoto2::gp_camera_get_config(self.camera, root.as_mut_ptr(), context.as_mut_ptr()));
+
+        Ok(::widget::from_libgphoto2(unsafe { root.assume_init() }))
+    }
+
+    /// Applies a preset to the camera.
+    ///
+    /// The preset's settings for the camera's model (see `Preset::settings_for()`) are applied like a
+    /// `ConfigSnapshot` (see `ConfigSnapshot::apply()`), so settings may be identified by name or
+    /// path. Settings that the camera does not have are skipped and reported as
+    /// `ApplyOutcome::Missing`.
+    ///
+    /// Returns the outcome for each setting of the preset.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use gphoto::SettingValue;
+    ///
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// let mut preset = gphoto::Preset::new("outdoor daylight");
+    /// preset.settings.insert("whitebalance".to_owned(), SettingValue::Choice("Daylight".to_owned()));
+    /// preset.settings.insert("iso".to_owned(), SettingValue::Choice("200".to_owned()));
+    ///
+    /// for (name, outcome) in camera.apply_preset(&mut context, &preset).unwrap() {
+    ///     if let gphoto::ApplyOutcome::Missing = outcome {
+    ///         println!("skipped {}", name);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera's configuration could not be retrieved:
+    ///
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn apply_preset(&mut self, context: &mut Context, preset: &Preset) -> ::Result<BTreeMap<String, ApplyOutcome>> {
+        let snapshot = ConfigSnapshot {
+            model: None,
+            settings: preset.settings_for(&self.abilities().model()),
+        };
+
+        snapshot.apply(self, context)
+    }
+
+    /// Searches the camera's configuration, returning the paths of the matching widgets.
+    ///
+    /// See `Widget::search()`.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// for path in camera.search_config(&mut context, "iso").unwrap() {
+    ///     println!("{} = {:?}", path, camera.get_setting(&mut context, &path).unwrap());
+    /// }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera's configuration could not be retrieved:
+    ///
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn search_config(&mut self, context: &mut Context, pattern: &str) -> ::Result<Vec<String>> {
+        Ok(self.config(context)?.search(pattern))
+    }
+
+    /// Writes a configuration tree to the camera.
+    ///
+    /// `root` must be the root of a tree retrieved with `config()`. Only the widgets whose values
+    /// were changed are written, so several settings can be changed with a single request to the
+    /// camera.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the configuration could not be written:
+    ///
+    /// * `InvalidInput` if the camera rejected a value.
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn set_config(&mut self, context: &mut Context, root: &mut Widget) -> ::Result<()> {
+        try_unsafe!(::gphoto2::gp_camera_set_config(self.camera, root.as_mut_ptr(), context.as_mut_ptr()));
+
+        Ok(())
+    }
+
+    /// Retrieves a single widget from the camera's configuration by name.
+    ///
+    /// Unlike `config()`, this function only retrieves the requested widget from the camera, which is
+    /// considerably faster for cameras that support it.
+    ///
+    /// If `name` starts with `/`, it is a path such as `"/main/imgsettings/iso"`, which identifies a
+    /// widget even if several widgets have the same name. The widget is then found in the full
+    /// configuration tree (see `Widget::child_by_path()`).
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the widget could not be retrieved:
+    ///
+    /// * `InvalidInput` if the camera does not have a setting with the given name or path.
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn config_widget(&mut self, context: &mut Context, name: &str) -> ::Result<Widget> {
+        if name.starts_with('/') {
+            return match self.config(context)?.child_by_path(name) {
+                Some(widget) => Ok(widget),
+                None => Err(::error::invalid_setting(name, None)),
+            };
+        }
+
+        let name = match CString::new(name) {
+            Ok(s) => s,
+            Err(_) => return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS)),
+        };
+
+        let mut widget = mem::MaybeUninit::uninit();
+
+        try_unsafe! {
+            ::ffi::gp_camera_get_single_config(self.camera,
+                                               name.as_ptr(),
+                                               widget.as_mut_ptr(),
+                                               context.as_mut_ptr())
+        };
+
+        Ok(::widget::from_libgphoto2(unsafe { widget.assume_init() }))
+    }
+
+    /// Returns the value of a single setting.
+    ///
+    /// The setting is identified by its name or path (see `config_widget()`).
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// println!("ISO = {:?}", camera.get_setting(&mut context, "iso").unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the setting could not be retrieved:
+    ///
+    /// * `InvalidInput` if the camera does not have a setting with the given name or path, or the
+    ///   setting's widget does not have a value.
+    pub fn get_setting(&mut self, context: &mut Context, name: &str) -> ::Result<SettingValue> {
+        match self.config_widget(context, name)?.value() {
+            Some(value) => Ok(value),
+            None => Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS)),
+        }
+    }
+
+    /// Changes the value of a single setting.
+    ///
+    /// The setting is identified by its name or path (see `config_widget()`). A setting identified
+    /// by its path is changed by writing the full configuration tree, so that the value can not be
+    /// applied to another widget with the same name.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use gphoto::SettingValue;
+    ///
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// camera.set_setting(&mut context, "iso", SettingValue::Choice("400".to_owned())).unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the setting could not be changed:
+    ///
+    /// * `InvalidInput` if the camera does not have a setting with the given name or path, or the
+    ///   value is not valid for the setting's widget (see `Widget::set_value()`).
+    pub fn set_setting(&mut self, context: &mut Context, name: &str, value: SettingValue) -> ::Result<()> {
+        if name.starts_with('/') {
+            let mut root = self.config(context)?;
+
+            match root.child_by_path(name) {
+                Some(mut widget) => widget.set_value(value)?,
+                None => return Err(::error::invalid_setting(name, None)),
+            }
+
+            return self.set_config(context, &mut root);
+        }
+
+        let mut widget = self.config_widget(context, name)?;
+
+        widget.set_value(value)?;
+
+        self.set_config_widget(context, &mut widget)
+    }
+
+    /// Writes a single widget's value to the camera.
+    ///
+    /// The widget is identified by its name, so it may have been retrieved with either
+    /// `config_widget()` or `config()`. Only the given widget is written to the camera.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use gphoto::SettingValue;
+    ///
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// let mut iso = camera.config_widget(&mut context, "iso").unwrap();
+    ///
+    /// if iso.choices().iter().any(|choice| choice == "800") {
+    ///     iso.set_value(SettingValue::Choice("800".to_owned())).unwrap();
+    ///     camera.set_config_widget(&mut context, &mut iso).unwrap();
+    /// }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the value could not be written:
+    ///
+    /// * `InvalidInput` if the camera does not have a setting with the widget's name, or the camera
+    ///   rejected the value.
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn set_config_widget(&mut self, context: &mut Context, widget: &mut Widget) -> ::Result<()> {
+        let name = match CString::new(widget.name().as_bytes()) {
+            Ok(s) => s,
+            Err(_) => return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS)),
+        };
+
+        try_unsafe! {
+            ::ffi::gp_camera_set_single_config(self.camera,
+                                               name.as_ptr(),
+                                               widget.as_mut_ptr(),
+                                               context.as_mut_ptr())
+        };
+
+        Ok(())
+    }
+
+    /// Presses a button, toggle or release widget by name and waits for the action to complete.
+    ///
+    /// Release widgets such as `eosremoterelease` are pressed fully and then released. If `name`
+    /// starts with `/`, it is a path that identifies the widget in the full configuration tree (see
+    /// `config_widget()`). See `Widget::press()`.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// camera.press(&mut context, "autofocusdrive").unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the widget could not be pressed:
+    ///
+    /// * `InvalidInput` if the camera does not have a setting with the given name, or the setting
+    ///   is neither a button, a toggle nor a release widget.
+    /// * `NotSupported` if a button does not have an action.
+    pub fn press(&mut self, context: &mut Context, name: &str) -> ::Result<Vec<CameraEvent>> {
+        let mut widget = self.config_widget(context, name)?;
+
+        widget.press(self, context)
+    }
+
+    /// Waits up to `timeout` for an event from the camera.
+    ///
+    /// Returns `CameraEvent::Timeout` if no event occurred before the timeout.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// loop {
+    ///     match camera.wait_for_event(&mut context, Duration::from_secs(10)).unwrap() {
+    ///         gphoto::CameraEvent::FileAdded(file) => {
+    ///             println!("new file: {}/{}", file.directory(), file.basename());
+    ///         },
+    ///         gphoto::CameraEvent::Timeout => break,
+    ///         _ => (),
+    ///     }
+    /// }
+    /// ```
+    pub fn wait_for_event(&mut self, context: &mut Context, timeout: Duration) -> ::Result<CameraEvent> {
+        let timeout = timeout.as_millis().min(c_int::MAX as u128) as c_int;
+
+        // Read the event type as an integer, because libgphoto2 may report event types that
+        // gphoto2-sys does not define.
+        let mut event_type: c_int = 0;
+        let mut data: *mut c_void = ptr::null_mut();
+
+        try_unsafe! {
+            ::gphoto2::gp_camera_wait_for_event(self.camera,
+                                                timeout,
+                                                &mut event_type as *mut c_int as *mut ::gphoto2::CameraEventType,
+                                                &mut data,
+                                                context.as_mut_ptr())
+        };
+
+        let event = unsafe { CameraEvent::from_libgphoto2(event_type, data) };
+
+        unsafe {
+            ::libc::free(data);
+        }
+
+        Ok(event)
+    }
+
+    /// Returns the time of the camera's clock.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the clock could not be read:
+    ///
+    /// * `NotSupported` if the camera does not have a clock setting.
+    pub fn clock(&mut self, context: &mut Context) -> ::Result<SystemTime> {
+        match self.clock_widget(context)?.value() {
+            Some(SettingValue::Date(seconds)) => Ok(util::time_from_unix(seconds)),
+            _ => Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_NOT_SUPPORTED)),
+        }
+    }
+
+    /// Sets the camera's clock.
+    ///
+    /// The camera's clock has a resolution of one second, so the time is truncated to whole seconds.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the clock could not be set:
+    ///
+    /// * `NotSupported` if the camera does not have a clock setting or its clock can not be set.
+    /// * `InvalidInput` if the time can not be represented by the camera.
+    pub fn set_clock(&mut self, context: &mut Context, time: SystemTime) -> ::Result<()> {
+        let mut widget = self.clock_widget(context)?;
+
+        if widget.readonly() {
+            return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_NOT_SUPPORTED));
+        }
+
+        widget.set_value(SettingValue::Date(util::time_to_unix(time)))?;
+
+        self.set_config_widget(context, &mut widget)
+    }
+
+    /// Measures the difference between the camera's clock and the host's clock.
+    ///
+    /// Returns the offset in seconds. A positive offset means that the camera's clock is ahead of the
+    /// host's clock.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// let offset = camera.clock_offset(&mut context).unwrap();
+    ///
+    /// if offset.abs() > 1 {
+    ///     println!("camera clock is off by {} seconds", offset);
+    ///     camera.sync_clock(&mut context).unwrap();
+    /// }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the clock could not be read:
+    ///
+    /// * `NotSupported` if the camera does not have a clock setting.
+    pub fn clock_offset(&mut self, context: &mut Context) -> ::Result<i64> {
+        let before = SystemTime::now();
+        let camera = self.clock(context)?;
+        let after = SystemTime::now();
+
+        // The camera's clock was read at some point during the request, so it is compared to the
+        // host's clock halfway through the request.
+        let host = match after.duration_since(before) {
+            Ok(elapsed) => before + elapsed / 2,
+            Err(_) => before,
+        };
+
+        let offset = match camera.duration_since(host) {
+            Ok(ahead) => ahead.as_secs_f64(),
+            Err(behind) => -behind.duration().as_secs_f64(),
+        };
+
+        Ok(offset.round() as i64)
+    }
+
+    /// Sets the camera's clock to the host's current time.
+    ///
+    /// See `set_clock()`.
+    pub fn sync_clock(&mut self, context: &mut Context) -> ::Result<()> {
+        self.set_clock(context, SystemTime::now())
+    }
+
+    fn clock_widget(&mut self, context: &mut Context) -> ::Result<Widget> {
+        for name in &CLOCK_SETTINGS {
+            let widget = match self.config_widget(context, name) {
+                Ok(widget) => widget,
+                Err(ref err) if err.kind() == ErrorKind::InvalidInput => continue,
+                Err(err) => return Err(err),
+            };
+
+            if widget.widget_type() == WidgetType::Date {
+                return Ok(widget);
+            }
+        }
+
+        Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_NOT_SUPPORTED))
+    }
+
     /// Returns the camera's summary.
     ///
     /// The summary typically contains non-configurable information about the camera, such as
@@ -173,12 +998,247 @@ impl Camera {
 }
 
 
+/// A builder for opening a camera with explicit connection options.
+///
+/// Any option that is not specified is detected automatically by `libgphoto2`. A builder without
+/// any options opens the first detected camera, just like `Camera::autodetect()`.
+///
+/// ## Example
+///
+/// ```no_run
+/// let mut context = gphoto::Context::new().unwrap();
+///
+/// let camera = gphoto::CameraBuilder::new()
+///     .model("Nikon DSC D750")
+///     .port("usb:001,007")
+///     .on_error(|msg| eprintln!("camera error: {}", msg))
+///     .build(&mut context)
+///     .unwrap();
+/// ```
+#[derive(Default)]
+pub struct CameraBuilder {
+    model: Option<String>,
+    port: Option<String>,
+    speed: Option<usize>,
+    ptpip_guid: Option<String>,
+    error_handler: Option<Handler>,
+    status_handler: Option<Handler>,
+    message_handler: Option<Handler>,
+}
+
+impl CameraBuilder {
+    /// Creates a builder without any connection options.
+    pub fn new() -> Self {
+        CameraBuilder::default()
+    }
+
+    /// Sets the camera model, e.g., `"Nikon DSC D750"`.
+    ///
+    /// If no port is specified, the camera is opened on the first port where a camera of this model
+    /// is detected.
+    pub fn model(mut self, model: &str) -> Self {
+        self.model = Some(model.to_owned());
+        self
+    }
+
+    /// Sets the path of the port the camera is connected to, e.g., `"usb:001,007"`.
+    ///
+    /// If no model is specified, the model of the camera connected to the port is detected.
+    pub fn port(mut self, path: &str) -> Self {
+        self.port = Some(path.to_owned());
+        self
+    }
+
+    /// Sets the speed of a serial port connection.
+    ///
+    /// The speed must be one of the speeds returned by `Abilities::speeds()` for the camera's model.
+    pub fn speed(mut self, speed: usize) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    /// Sets the GUID that identifies this client to PTP/IP cameras.
+    ///
+    /// The GUID is written as 16 hexadecimal bytes separated by colons, e.g.,
+    /// `"0a:1b:2c:3d:4e:5f:60:71:82:93:a4:b5:c6:d7:e8:f9"`. It is stored in the `libgphoto2`
+    /// settings when the camera is opened, so it is also used by later PTP/IP connections.
+    pub fn ptpip_guid(mut self, guid: &str) -> Self {
+        self.ptpip_guid = Some(guid.to_owned());
+        self
+    }
+
+    /// Sets a function that is called with error messages while the camera is in use.
+    ///
+    /// See `Context::set_error_handler()`.
+    pub fn on_error<F: FnMut(&str) + 'static>(mut self, handler: F) -> Self {
+        self.error_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Sets a function that is called with status messages while the camera is in use.
+    ///
+    /// See `Context::set_status_handler()`.
+    pub fn on_status<F: FnMut(&str) + 'static>(mut self, handler: F) -> Self {
+        self.status_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Sets a function that is called with informational messages while the camera is in use.
+    ///
+    /// See `Context::set_message_handler()`.
+    pub fn on_message<F: FnMut(&str) + 'static>(mut self, handler: F) -> Self {
+        self.message_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Opens the camera.
+    ///
+    /// Any message handlers are installed on `context` and remain in effect after the camera has
+    /// been opened.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera could not be opened:
+    ///
+    /// * `ModelNotFound` if no camera driver supports the model or no camera of the model is
+    ///   connected.
+    /// * `UnknownPort` if the port path does not match any port on the system.
+    /// * `InvalidInput` if a speed was given without a model or port, the speed is not supported
+    ///   by the model, or the PTP/IP GUID is malformed.
+    pub fn build(self, context: &mut Context) -> ::Result<Camera> {
+        if let Some(ref guid) = self.ptpip_guid {
+            if !util::is_valid_guid(guid) {
+                return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS));
+            }
+
+            let id = CString::new("ptp2_ip").unwrap();
+            let key = CString::new("guid").unwrap();
+            let value = CString::new(guid.as_str()).unwrap();
+
+            try_unsafe!(::ffi::gp_setting_set(id.as_ptr(), key.as_ptr(), value.as_ptr()));
+        }
+
+        if let Some(mut handler) = self.error_handler {
+            context.set_error_handler(move |msg| handler(msg));
+        }
+
+        if let Some(mut handler) = self.status_handler {
+            context.set_status_handler(move |msg| handler(msg));
+        }
+
+        if let Some(mut handler) = self.message_handler {
+            context.set_message_handler(move |msg| handler(msg));
+        }
+
+        let mut camera = Camera::new()?;
+
+        let abilities = match self.model {
+            Some(ref model) => match AbilitiesList::new(context)?.find_model(model) {
+                Some(a) => Some(a),
+                None => return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_MODEL_NOT_FOUND)),
+            },
+            None => None,
+        };
+
+        let port_path = match (self.port, abilities.as_ref()) {
+            (Some(path), _) => Some(path),
+            (None, Some(abilities)) => {
+                let model = abilities.model();
+
+                match Camera::detect_all(context)?.into_iter().find(|c| c.model().eq_ignore_ascii_case(&model)) {
+                    Some(detected) => Some(detected.port_path),
+                    None => return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_MODEL_NOT_FOUND)),
+                }
+            },
+            (None, None) => None,
+        };
+
+        if let Some(ref abilities) = abilities {
+            try_unsafe!(::gphoto2::gp_camera_set_abilities(camera.camera, ptr::read(abilities.as_ptr())));
+        }
+
+        if let Some(ref path) = port_path {
+            camera.set_port_path(path)?;
+        }
+
+        if let Some(speed) = self.speed {
+            if port_path.is_none() {
+                return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS));
+            }
+
+            if let Some(ref abilities) = abilities {
+                let speeds = abilities.speeds();
+
+                if !speeds.is_empty() && !speeds.contains(&speed) {
+                    return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS));
+                }
+            }
+
+            try_unsafe!(::gphoto2::gp_camera_set_port_speed(camera.camera, speed as c_int));
+        }
+
+        try_unsafe!(::gphoto2::gp_camera_init(camera.camera, context.as_mut_ptr()));
+
+        camera.remember_serial_number(context);
+
+        Ok(camera)
+    }
+}
+
+
+/// A camera that was found by `Camera::detect_all()`.
+#[derive(Debug,PartialEq,Eq,Clone,Hash)]
+pub struct DetectedCamera {
+    model: String,
+    port_path: String,
+}
+
+impl DetectedCamera {
+    /// Returns the name of the camera's model.
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Returns the path of the port the camera is connected to.
+    pub fn port_path(&self) -> &str {
+        &self.port_path
+    }
+}
+
+
 /// A file stored on a camera's storage.
 pub struct CameraFile {
     inner: ::gphoto2::CameraFilePath,
 }
 
+impl Clone for CameraFile {
+    fn clone(&self) -> Self {
+        // CameraFilePath only contains character arrays, so a bitwise copy is a complete copy.
+        CameraFile { inner: unsafe { ptr::read(&self.inner) } }
+    }
+}
+
+impl fmt::Debug for CameraFile {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("CameraFile")
+            .field("directory", &self.directory())
+            .field("basename", &self.basename())
+            .finish()
+    }
+}
+
 impl CameraFile {
+    fn new(directory: &str, basename: &str) -> ::Result<Self> {
+        // CameraFilePath only contains character arrays, so all zeros is a valid empty path.
+        let mut inner: ::gphoto2::CameraFilePath = unsafe { mem::zeroed() };
+
+        if !util::copy_to_c_array(&mut inner.folder, directory) || !util::copy_to_c_array(&mut inner.name, basename) {
+            return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS));
+        }
+
+        Ok(CameraFile { inner })
+    }
+
     /// Returns the directory that the file is stored in.
     pub fn directory(&self) -> Cow<str> {
         unsafe {
@@ -194,8 +1254,103 @@ impl CameraFile {
     }
 }
 
+/// Events reported by a camera.
+#[derive(Debug,Clone)]
+pub enum CameraEvent {
+    /// An event that does not have a specific type, with its description.
+    ///
+    /// Drivers report other events as text, e.g., `"PTP Property d10b changed"`.
+    Unknown(String),
+
+    /// No event occurred before the timeout.
+    Timeout,
+
+    /// A file was added to the camera's storage, e.g., by a capture.
+    FileAdded(CameraFile),
+
+    /// A folder was added to the camera's storage.
+    ///
+    /// The event's `directory()` is the parent folder, and its `basename()` is the name of the new
+    /// folder.
+    FolderAdded(CameraFile),
+
+    /// A capture completed.
+    CaptureComplete,
+
+    /// A file on the camera's storage was changed.
+    FileChanged(CameraFile),
+}
+
+impl CameraEvent {
+    unsafe fn from_libgphoto2(event_type: c_int, data: *mut c_void) -> Self {
+        let file = || {
+            if data.is_null() {
+                None
+            }
+            else {
+                Some(CameraFile { inner: ptr::read(data as *const ::gphoto2::CameraFilePath) })
+            }
+        };
+
+        match event_type {
+            1 => CameraEvent::Timeout,
+            2 => file().map_or(CameraEvent::Unknown(String::new()), CameraEvent::FileAdded),
+            3 => file().map_or(CameraEvent::Unknown(String::new()), CameraEvent::FolderAdded),
+            4 => CameraEvent::CaptureComplete,
+            5 => file().map_or(CameraEvent::Unknown(String::new()), CameraEvent::FileChanged),
+            0 if !data.is_null() => CameraEvent::Unknown(String::from_utf8_lossy(CStr::from_ptr(data as *const c_char).to_bytes()).into_owned()),
+            _ => CameraEvent::Unknown(String::new()),
+        }
+    }
+}
+
 mod util {
     use std::ffi::CStr;
+    use std::time::{Duration,SystemTime,UNIX_EPOCH};
+
+    use ::libc::c_char;
+
+    /// Checks that a PTP/IP GUID consists of 16 colon-separated hexadecimal bytes.
+    pub fn is_valid_guid(guid: &str) -> bool {
+        let bytes: Vec<&str> = guid.split(':').collect();
+
+        bytes.len() == 16 && bytes.iter().all(|b| b.len() == 2 && b.chars().all(|c| c.is_ascii_hexdigit()))
+    }
+
+    pub fn time_from_unix(seconds: i64) -> SystemTime {
+        if seconds >= 0 {
+            UNIX_EPOCH + Duration::from_secs(seconds as u64)
+        }
+        else {
+            UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs())
+        }
+    }
+
+    pub fn time_to_unix(time: SystemTime) -> i64 {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_secs() as i64,
+            Err(before) => -(before.duration().as_secs() as i64),
+        }
+    }
+
+    /// Copies a string into a nul-terminated character array.
+    ///
+    /// Returns `false` if the string contains a nul byte or does not fit.
+    pub fn copy_to_c_array(dest: &mut [c_char], src: &str) -> bool {
+        let bytes = src.as_bytes();
+
+        if bytes.len() >= dest.len() || bytes.contains(&0) {
+            return false;
+        }
+
+        for (d, &b) in dest.iter_mut().zip(bytes) {
+            *d = b as c_char;
+        }
+
+        dest[bytes.len()] = 0;
+
+        true
+    }
 
     pub fn camera_text_to_string(camera_text: *mut::gphoto2::CameraText) -> ::Result<String> {
         let length = unsafe {
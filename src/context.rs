@@ -1,8 +1,25 @@
+use std::ffi::CStr;
+
+use ::libc::{c_char,c_void};
+
 use ::handle::{Handle,HandleMut};
 
 /// A `libgphoto2` library context.
 pub struct Context {
     context: *mut ::gphoto2::GPContext,
+
+    // Boxed so that the address handed to libgphoto2 remains valid when the Context is moved.
+    handlers: Box<Handlers>,
+}
+
+/// A function that receives messages reported by `libgphoto2`.
+pub type Handler = Box<dyn FnMut(&str)>;
+
+#[derive(Default)]
+struct Handlers {
+    error: Option<Handler>,
+    status: Option<Handler>,
+    message: Option<Handler>,
 }
 
 impl Context {
@@ -11,12 +28,45 @@ impl Context {
         let ptr = unsafe { ::gphoto2::gp_context_new() };
 
         if !ptr.is_null() {
-            Ok(Context { context: ptr })
+            Ok(Context { context: ptr, handlers: Box::new(Handlers::default()) })
         }
         else {
             Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_NO_MEMORY))
         }
     }
+
+    /// Sets a function that is called with error messages reported by `libgphoto2`.
+    pub fn set_error_handler<F: FnMut(&str) + 'static>(&mut self, handler: F) {
+        self.handlers.error = Some(Box::new(handler));
+
+        unsafe {
+            ::gphoto2::gp_context_set_error_func(self.context, error_func, self.handlers_ptr());
+        }
+    }
+
+    /// Sets a function that is called with status messages reported by `libgphoto2`.
+    ///
+    /// Status messages describe the progress of an operation, e.g., `"Downloading file..."`.
+    pub fn set_status_handler<F: FnMut(&str) + 'static>(&mut self, handler: F) {
+        self.handlers.status = Some(Box::new(handler));
+
+        unsafe {
+            ::gphoto2::gp_context_set_status_func(self.context, status_func, self.handlers_ptr());
+        }
+    }
+
+    /// Sets a function that is called with informational messages reported by `libgphoto2`.
+    pub fn set_message_handler<F: FnMut(&str) + 'static>(&mut self, handler: F) {
+        self.handlers.message = Some(Box::new(handler));
+
+        unsafe {
+            ::gphoto2::gp_context_set_message_func(self.context, message_func, self.handlers_ptr());
+        }
+    }
+
+    fn handlers_ptr(&mut self) -> *mut c_void {
+        &mut *self.handlers as *mut Handlers as *mut c_void
+    }
 }
 
 impl Drop for Context {
@@ -40,3 +90,27 @@ impl HandleMut<::gphoto2::GPContext> for Context {
         self.context
     }
 }
+
+extern "C" fn error_func(_context: *mut ::gphoto2::GPContext, text: *const c_char, data: *mut c_void) {
+    let handlers = unsafe { &mut *(data as *mut Handlers) };
+
+    if let Some(ref mut handler) = handlers.error {
+        handler(&unsafe { String::from_utf8_lossy(CStr::from_ptr(text).to_bytes()) });
+    }
+}
+
+extern "C" fn status_func(_context: *mut ::gphoto2::GPContext, text: *const c_char, data: *mut c_void) {
+    let handlers = unsafe { &mut *(data as *mut Handlers) };
+
+    if let Some(ref mut handler) = handlers.status {
+        handler(&unsafe { String::from_utf8_lossy(CStr::from_ptr(text).to_bytes()) });
+    }
+}
+
+extern "C" fn message_func(_context: *mut ::gphoto2::GPContext, text: *const c_char, data: *mut c_void) {
+    let handlers = unsafe { &mut *(data as *mut Handlers) };
+
+    if let Some(ref mut handler) = handlers.message {
+        handler(&unsafe { String::from_utf8_lossy(CStr::from_ptr(text).to_bytes()) });
+    }
+}
@@ -24,6 +24,9 @@ pub enum ErrorKind {
     /// Specified camera model was not found.
     ModelNotFound,
 
+    /// Specified port was not found.
+    UnknownPort,
+
     /// File already exists.
     FileExists,
 
@@ -51,6 +54,9 @@ pub enum ErrorKind {
     /// An error was reported by the operating system.
     OSFailure,
 
+    /// An I/O error occurred while communicating with the device.
+    IO,
+
     /// Not enough space when uploading a file.
     NoSpace,
 
@@ -62,6 +68,8 @@ pub enum ErrorKind {
 #[derive(Debug)]
 pub struct Error {
     err: c_int,
+    setting: Option<String>,
+    suggestion: Option<String>,
 }
 
 impl Error {
@@ -73,6 +81,7 @@ impl Error {
             ::gphoto2::GP_ERROR_CORRUPTED_DATA      => ErrorKind::CorruptedData,
             ::gphoto2::GP_ERROR_FILE_EXISTS         => ErrorKind::FileExists,
             ::gphoto2::GP_ERROR_MODEL_NOT_FOUND     => ErrorKind::ModelNotFound,
+            ::gphoto2::GP_ERROR_UNKNOWN_PORT        => ErrorKind::UnknownPort,
             ::gphoto2::GP_ERROR_DIRECTORY_NOT_FOUND => ErrorKind::DirectoryNotFound,
             ::gphoto2::GP_ERROR_FILE_NOT_FOUND      => ErrorKind::FileNotFound,
             ::gphoto2::GP_ERROR_DIRECTORY_EXISTS    => ErrorKind::DirectoryExists,
@@ -83,10 +92,36 @@ impl Error {
             ::gphoto2::GP_ERROR_OS_FAILURE          => ErrorKind::OSFailure,
             ::gphoto2::GP_ERROR_NO_SPACE            => ErrorKind::NoSpace,
 
+            ::gphoto2::GP_ERROR_IO
+            | ::gphoto2::GP_ERROR_IO_INIT
+            | ::gphoto2::GP_ERROR_IO_READ
+            | ::gphoto2::GP_ERROR_IO_WRITE
+            | ::gphoto2::GP_ERROR_IO_UPDATE
+            | ::gphoto2::GP_ERROR_IO_SERIAL_SPEED
+            | ::gphoto2::GP_ERROR_IO_USB_CLEAR_HALT
+            | ::gphoto2::GP_ERROR_IO_USB_FIND
+            | ::gphoto2::GP_ERROR_IO_USB_CLAIM
+            | ::gphoto2::GP_ERROR_IO_LOCK           => ErrorKind::IO,
+
             ::gphoto2::GP_ERROR | _ => ErrorKind::Other
         }
     }
 
+    /// Returns the name of the setting that the error refers to.
+    ///
+    /// This is set for `InvalidInput` errors caused by a value that is not valid for a setting.
+    pub fn setting(&self) -> Option<&str> {
+        self.setting.as_ref().map(|s| &s[..])
+    }
+
+    /// Returns a valid value that is closest to the rejected value.
+    ///
+    /// This is set for `InvalidInput` errors caused by a value that is not one of a setting's
+    /// choices or is outside of a setting's range.
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_ref().map(|s| &s[..])
+    }
+
     /// Returns an error message.
     pub fn message(&self) -> &'static str {
         unsafe {
@@ -97,7 +132,17 @@ impl Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> StdResult<(),fmt::Error> {
-        fmt.write_str(self.message())
+        fmt.write_str(self.message())?;
+
+        if let Some(ref setting) = self.setting {
+            write!(fmt, " for setting {:?}", setting)?;
+        }
+
+        if let Some(ref suggestion) = self.suggestion {
+            write!(fmt, " (closest valid value: {:?})", suggestion)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -110,7 +155,21 @@ impl StdError for Error {
 
 #[doc(hidden)]
 pub fn from_libgphoto2(err: c_int) -> Error {
-    Error { err }
+    Error {
+        err,
+        setting: None,
+        suggestion: None,
+    }
+}
+
+/// Creates an `InvalidInput` error for a value that is not valid for a setting.
+#[doc(hidden)]
+pub fn invalid_setting(setting: &str, suggestion: Option<String>) -> Error {
+    Error {
+        err: ::gphoto2::GP_ERROR_BAD_PARAMETERS,
+        setting: Some(setting.to_owned()),
+        suggestion,
+    }
 }
 
 #[doc(hidden)]
@@ -0,0 +1,530 @@
+use std::cmp::Ordering;
+use std::error::Error as StdError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Exposure parameters whose values can be compared in stops.
+///
+/// Cameras report exposure parameters as `Radio` or `Menu` widgets whose choices are strings such
+/// as `"1/250"` or `"f/5.6"`. Types that implement `ExposureSetting` parse those strings, and
+/// `step()` uses a camera's list of choices to move a setting by a number of stops.
+///
+/// Stops are counted in the direction of more light reaching the image: a longer shutter speed, a
+/// wider aperture, a higher ISO or a larger exposure compensation is a positive number of stops.
+pub trait ExposureSetting: FromStr {
+    /// Returns the position of the value on a scale of stops.
+    ///
+    /// The scale's origin is arbitrary, so only differences between values are meaningful. Returns
+    /// `None` for values without a fixed exposure, such as bulb mode or automatic ISO.
+    fn stops(&self) -> Option<f64>;
+
+    /// Selects the choice that changes the exposure by `stops` relative to this value.
+    ///
+    /// `choices` is the list of choices of the setting's widget, as returned by
+    /// `Widget::choices()`. Choices that can not be parsed or have no fixed exposure are ignored.
+    /// Among the choices in the requested direction, the one closest to the requested change is
+    /// returned, so stepping by one stop works for cameras with third-stop and half-stop increments
+    /// alike.
+    ///
+    /// Returns `None` if this value has no fixed exposure or no choice lies in the requested
+    /// direction.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use gphoto::{ExposureSetting,ShutterSpeed};
+    ///
+    /// let choices = ["1/60", "1/80", "1/100", "1/125", "1/160", "1/200", "1/250"];
+    /// let current: ShutterSpeed = "1/125".parse().unwrap();
+    ///
+    /// // one stop faster
+    /// assert_eq!(Some("1/250"), current.step(&choices, -1.0));
+    ///
+    /// // one third of a stop slower
+    /// assert_eq!(Some("1/100"), current.step(&choices, 1.0 / 3.0));
+    /// ```
+    fn step<'a, S: AsRef<str>>(&self, choices: &'a [S], stops: f64) -> Option<&'a str> {
+        // Tolerance for comparing positions, well below the smallest increment of a third of a stop.
+        const EPSILON: f64 = 0.05;
+
+        let current = self.stops()?;
+        let target = current + stops;
+
+        let mut best: Option<(&'a str, f64)> = None;
+
+        for choice in choices {
+            let choice = choice.as_ref();
+
+            let position = match choice.parse::<Self>().ok().and_then(|value| value.stops()) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            let in_direction = if stops > 0.0 {
+                position > current + EPSILON
+            }
+            else if stops < 0.0 {
+                position < current - EPSILON
+            }
+            else {
+                true
+            };
+
+            if !in_direction {
+                continue;
+            }
+
+            let distance = (position - target).abs();
+
+            match best {
+                Some((_, best_distance)) if best_distance <= distance => (),
+                _ => best = Some((choice, distance)),
+            }
+        }
+
+        best.map(|(choice, _)| choice)
+    }
+}
+
+/// An error that occurs when an exposure parameter can not be parsed.
+#[derive(Debug,PartialEq,Eq,Clone)]
+pub struct ParseExposureError {
+    input: String,
+}
+
+impl ParseExposureError {
+    fn new(input: &str) -> Self {
+        ParseExposureError { input: input.to_owned() }
+    }
+}
+
+impl fmt::Display for ParseExposureError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "invalid exposure value: {:?}", self.input)
+    }
+}
+
+impl StdError for ParseExposureError {}
+
+/// A shutter speed.
+///
+/// Shutter speeds are parsed from strings such as `"1/250"`, `"0.3"`, `"30"`, `"2.5s"` or `"bulb"`
+/// and formatted in the same style. Shutter speeds are ordered from fastest to slowest, with bulb
+/// and time modes after all timed exposures.
+///
+/// ## Example
+///
+/// ```
+/// use gphoto::ShutterSpeed;
+///
+/// let fast: ShutterSpeed = "1/250".parse().unwrap();
+/// let slow: ShutterSpeed = "0.5".parse().unwrap();
+///
+/// assert!(fast < slow);
+/// assert_eq!(Some(0.5), slow.seconds());
+/// assert_eq!("1/250", fast.to_string());
+/// assert!("bulb".parse::<ShutterSpeed>().unwrap().is_bulb());
+/// ```
+#[derive(Debug,PartialEq,Eq,Clone,Copy,Hash)]
+pub struct ShutterSpeed {
+    kind: ShutterKind,
+}
+
+#[derive(Debug,PartialEq,Eq,Clone,Copy,Hash)]
+enum ShutterKind {
+    // Reduced fraction of seconds, so that equal durations compare equal.
+    Timed(u32, u32),
+    Bulb,
+    Time,
+}
+
+impl ShutterSpeed {
+    /// Creates a timed shutter speed of `numerator / denominator` seconds.
+    ///
+    /// Returns `None` if either part is zero.
+    pub fn from_fraction(numerator: u32, denominator: u32) -> Option<Self> {
+        if numerator == 0 || denominator == 0 {
+            return None;
+        }
+
+        let divisor = util::gcd(numerator as u64, denominator as u64) as u32;
+
+        Some(ShutterSpeed { kind: ShutterKind::Timed(numerator / divisor, denominator / divisor) })
+    }
+
+    /// Returns the shutter speed for bulb mode, where the shutter is open while the release is held.
+    pub fn bulb() -> Self {
+        ShutterSpeed { kind: ShutterKind::Bulb }
+    }
+
+    /// Returns the shutter speed for time mode, where the shutter is opened and closed by separate
+    /// presses of the release.
+    pub fn time() -> Self {
+        ShutterSpeed { kind: ShutterKind::Time }
+    }
+
+    /// Returns `true` for bulb mode.
+    pub fn is_bulb(&self) -> bool {
+        self.kind == ShutterKind::Bulb
+    }
+
+    /// Returns `true` for time mode.
+    pub fn is_time(&self) -> bool {
+        self.kind == ShutterKind::Time
+    }
+
+    /// Returns the duration of a timed exposure in seconds.
+    ///
+    /// Returns `None` for bulb and time modes.
+    pub fn seconds(&self) -> Option<f64> {
+        match self.kind {
+            ShutterKind::Timed(numerator, denominator) => Some(numerator as f64 / denominator as f64),
+            ShutterKind::Bulb | ShutterKind::Time => None,
+        }
+    }
+}
+
+impl ExposureSetting for ShutterSpeed {
+    fn stops(&self) -> Option<f64> {
+        self.seconds().map(f64::log2)
+    }
+}
+
+impl Ord for ShutterSpeed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.kind, other.kind) {
+            (ShutterKind::Timed(n1, d1), ShutterKind::Timed(n2, d2)) => {
+                (n1 as u64 * d2 as u64).cmp(&(n2 as u64 * d1 as u64))
+            },
+            (ShutterKind::Timed(..), _) => Ordering::Less,
+            (_, ShutterKind::Timed(..)) => Ordering::Greater,
+            (ShutterKind::Bulb, ShutterKind::Time) => Ordering::Less,
+            (ShutterKind::Time, ShutterKind::Bulb) => Ordering::Greater,
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+impl PartialOrd for ShutterSpeed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for ShutterSpeed {
+    type Err = ParseExposureError;
+
+    fn from_str(s: &str) -> Result<Self, ParseExposureError> {
+        let value = s.trim().to_lowercase();
+
+        match value.as_str() {
+            "bulb" => return Ok(ShutterSpeed::bulb()),
+            "time" => return Ok(ShutterSpeed::time()),
+            _ => (),
+        }
+
+        let value = value.trim_end_matches(&['s', '"'][..]).trim();
+
+        let fraction = match value.find('/') {
+            Some(i) => {
+                match (value[..i].trim().parse::<u32>(), value[i + 1..].trim().parse::<u32>()) {
+                    (Ok(numerator), Ok(denominator)) => Some((numerator, denominator)),
+                    _ => None,
+                }
+            },
+            None => util::parse_decimal(value),
+        };
+
+        fraction.and_then(|(numerator, denominator)| ShutterSpeed::from_fraction(numerator, denominator))
+            .ok_or_else(|| ParseExposureError::new(s))
+    }
+}
+
+impl fmt::Display for ShutterSpeed {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ShutterKind::Bulb => fmt.write_str("bulb"),
+            ShutterKind::Time => fmt.write_str("time"),
+            ShutterKind::Timed(numerator, 1) => write!(fmt, "{}", numerator),
+            ShutterKind::Timed(1, denominator) => write!(fmt, "1/{}", denominator),
+            ShutterKind::Timed(numerator, denominator) if util::is_terminating(denominator) => {
+                write!(fmt, "{}", numerator as f64 / denominator as f64)
+            },
+            ShutterKind::Timed(numerator, denominator) => write!(fmt, "{}/{}", numerator, denominator),
+        }
+    }
+}
+
+/// An aperture, expressed as an f-number.
+///
+/// Apertures are parsed from strings such as `"f/5.6"`, `"F5.6"` or `"5.6"` and formatted as
+/// `"f/5.6"`. Apertures are ordered by f-number, from widest to narrowest.
+///
+/// ## Example
+///
+/// ```
+/// use gphoto::{Aperture,ExposureSetting};
+///
+/// let aperture: Aperture = "f/5.6".parse().unwrap();
+///
+/// assert_eq!(5.6, aperture.f_number());
+/// assert_eq!("f/5.6", aperture.to_string());
+///
+/// // stopping down by one stop
+/// let choices = ["f/4", "f/4.5", "f/5", "f/5.6", "f/6.3", "f/7.1", "f/8"];
+/// assert_eq!(Some("f/8"), aperture.step(&choices, -1.0));
+/// ```
+#[derive(Debug,PartialEq,Eq,PartialOrd,Ord,Clone,Copy,Hash)]
+pub struct Aperture {
+    hundredths: u32,
+}
+
+impl Aperture {
+    /// Creates an aperture from an f-number, which is rounded to two decimal places.
+    ///
+    /// Returns `None` unless the f-number is positive and finite.
+    pub fn from_f_number(f_number: f64) -> Option<Self> {
+        if !f_number.is_finite() || f_number <= 0.0 || f_number * 100.0 > u32::MAX as f64 {
+            return None;
+        }
+
+        match (f_number * 100.0).round() as u32 {
+            0 => None,
+            hundredths => Some(Aperture { hundredths }),
+        }
+    }
+
+    /// Returns the f-number.
+    pub fn f_number(&self) -> f64 {
+        self.hundredths as f64 / 100.0
+    }
+}
+
+impl ExposureSetting for Aperture {
+    fn stops(&self) -> Option<f64> {
+        // Each stop multiplies the f-number by the square root of two.
+        Some(-2.0 * self.f_number().log2())
+    }
+}
+
+impl FromStr for Aperture {
+    type Err = ParseExposureError;
+
+    fn from_str(s: &str) -> Result<Self, ParseExposureError> {
+        let value = s.trim().to_lowercase();
+
+        let value = value.strip_prefix("f/")
+            .or_else(|| value.strip_prefix('f'))
+            .unwrap_or(&value);
+
+        util::parse_decimal(value.trim())
+            .and_then(|(numerator, denominator)| Aperture::from_f_number(numerator as f64 / denominator as f64))
+            .ok_or_else(|| ParseExposureError::new(s))
+    }
+}
+
+impl fmt::Display for Aperture {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "f/{}", self.f_number())
+    }
+}
+
+/// An ISO sensitivity.
+///
+/// ISO values are parsed from strings such as `"400"`, `"ISO 400"` or `"Auto ISO"` and formatted
+/// as `"400"` or `"Auto"`. Automatic ISO is ordered before all fixed values.
+///
+/// ## Example
+///
+/// ```
+/// use gphoto::{ExposureSetting,Iso};
+///
+/// assert_eq!(Iso::Value(400), "400".parse().unwrap());
+/// assert_eq!(Iso::Auto, "Auto ISO".parse().unwrap());
+///
+/// let choices = ["Auto", "100", "200", "400", "800", "1600"];
+/// assert_eq!(Some("1600"), Iso::Value(400).step(&choices, 2.0));
+/// ```
+#[derive(Debug,PartialEq,Eq,PartialOrd,Ord,Clone,Copy,Hash)]
+pub enum Iso {
+    /// The camera selects the ISO sensitivity automatically.
+    Auto,
+
+    /// A fixed ISO sensitivity.
+    Value(u32),
+}
+
+impl ExposureSetting for Iso {
+    fn stops(&self) -> Option<f64> {
+        match *self {
+            Iso::Auto => None,
+            Iso::Value(value) => Some((value as f64).log2()),
+        }
+    }
+}
+
+impl FromStr for Iso {
+    type Err = ParseExposureError;
+
+    fn from_str(s: &str) -> Result<Self, ParseExposureError> {
+        let value = s.trim().to_lowercase();
+
+        if value.contains("auto") {
+            return Ok(Iso::Auto);
+        }
+
+        let value = value.trim_start_matches("iso").trim();
+
+        match value.parse::<u32>() {
+            Ok(value) if value > 0 => Ok(Iso::Value(value)),
+            _ => Err(ParseExposureError::new(s)),
+        }
+    }
+}
+
+impl fmt::Display for Iso {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Iso::Auto => fmt.write_str("Auto"),
+            Iso::Value(value) => write!(fmt, "{}", value),
+        }
+    }
+}
+
+/// An exposure compensation in stops (EV).
+///
+/// Exposure compensation values are parsed from strings such as `"-1"`, `"+0.3"`, `"0.333"`,
+/// `"-2/3"` or `"+1 1/3"` and formatted as a signed decimal such as `"+0.333"`. Values are stored
+/// with a resolution of a thousandth of a stop.
+///
+/// ## Example
+///
+/// ```
+/// use gphoto::ExposureCompensation;
+///
+/// let ec: ExposureCompensation = "-1 1/3".parse().unwrap();
+///
+/// assert_eq!(-1.333, ec.ev());
+/// assert_eq!("-1.333", ec.to_string());
+/// assert!(ec < "0".parse().unwrap());
+/// ```
+#[derive(Debug,PartialEq,Eq,PartialOrd,Ord,Clone,Copy,Hash)]
+pub struct ExposureCompensation {
+    thousandths: i32,
+}
+
+impl ExposureCompensation {
+    /// Creates an exposure compensation of `ev` stops, which is rounded to a thousandth of a stop.
+    ///
+    /// Returns `None` unless `ev` is finite and within a reasonable range.
+    pub fn from_ev(ev: f64) -> Option<Self> {
+        if !ev.is_finite() || ev.abs() > 1000.0 {
+            return None;
+        }
+
+        Some(ExposureCompensation { thousandths: (ev * 1000.0).round() as i32 })
+    }
+
+    /// Returns the exposure compensation in stops.
+    pub fn ev(&self) -> f64 {
+        self.thousandths as f64 / 1000.0
+    }
+}
+
+impl ExposureSetting for ExposureCompensation {
+    fn stops(&self) -> Option<f64> {
+        Some(self.ev())
+    }
+}
+
+impl FromStr for ExposureCompensation {
+    type Err = ParseExposureError;
+
+    fn from_str(s: &str) -> Result<Self, ParseExposureError> {
+        let value = s.trim().replace('\u{2212}', "-");
+
+        let (sign, value) = if let Some(value) = value.strip_prefix('-') {
+            (-1.0, value.trim())
+        }
+        else if let Some(value) = value.strip_prefix('+') {
+            (1.0, value.trim())
+        }
+        else {
+            (1.0, value.trim())
+        };
+
+        // Values are either decimals, fractions ("2/3") or mixed numbers ("1 1/3").
+        let (whole, fraction) = match value.find(' ') {
+            Some(i) => (&value[..i], value[i..].trim()),
+            None if value.contains('/') => ("0", value),
+            None => (value, "0"),
+        };
+
+        let whole = util::parse_decimal(whole).map(|(n, d)| n as f64 / d as f64);
+
+        let fraction = match fraction.find('/') {
+            Some(i) => {
+                match (fraction[..i].trim().parse::<u32>(), fraction[i + 1..].trim().parse::<u32>()) {
+                    (Ok(n), Ok(d)) if d > 0 => Some(n as f64 / d as f64),
+                    _ => None,
+                }
+            },
+            None => util::parse_decimal(fraction).map(|(n, d)| n as f64 / d as f64),
+        };
+
+        match (whole, fraction) {
+            (Some(whole), Some(fraction)) => ExposureCompensation::from_ev(sign * (whole + fraction)),
+            _ => None,
+        }.ok_or_else(|| ParseExposureError::new(s))
+    }
+}
+
+impl fmt::Display for ExposureCompensation {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.thousandths > 0 {
+            write!(fmt, "+{}", self.ev())
+        }
+        else {
+            write!(fmt, "{}", self.ev())
+        }
+    }
+}
+
+mod util {
+    /// Parses an unsigned decimal number into a fraction, e.g., `"2.5"` into `(25, 10)`.
+    pub fn parse_decimal(s: &str) -> Option<(u32, u32)> {
+        let (whole, decimals) = match s.find('.') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => (s, ""),
+        };
+
+        if whole.is_empty() && decimals.is_empty() {
+            return None;
+        }
+
+        if !whole.chars().chain(decimals.chars()).all(|c| c.is_ascii_digit()) || decimals.len() > 6 {
+            return None;
+        }
+
+        let denominator = 10u32.pow(decimals.len() as u32);
+        let whole: u32 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+        let decimals: u32 = if decimals.is_empty() { 0 } else { decimals.parse().ok()? };
+
+        whole.checked_mul(denominator)?.checked_add(decimals).map(|numerator| (numerator, denominator))
+    }
+
+    pub fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+
+    /// Returns `true` if a fraction with the denominator has a finite decimal representation.
+    pub fn is_terminating(denominator: u32) -> bool {
+        let mut denominator = denominator >> denominator.trailing_zeros();
+
+        while let (quotient, 0) = (denominator / 5, denominator % 5) {
+            denominator = quotient;
+        }
+
+        denominator == 1
+    }
+}
@@ -0,0 +1,12 @@
+// exports from libgphoto2 that are missing from gphoto2-sys
+
+use ::libc::{c_char,c_int};
+
+extern "C" {
+    // gphoto2-camera.h
+    pub fn gp_camera_get_single_config(camera: *mut ::gphoto2::Camera, name: *const c_char, widget: *mut *mut ::gphoto2::CameraWidget, context: *mut ::gphoto2::GPContext) -> c_int;
+    pub fn gp_camera_set_single_config(camera: *mut ::gphoto2::Camera, name: *const c_char, widget: *mut ::gphoto2::CameraWidget, context: *mut ::gphoto2::GPContext) -> c_int;
+
+    // gphoto2-setting.h
+    pub fn gp_setting_set(id: *const c_char, key: *const c_char, value: *const c_char) -> c_int;
+}
@@ -1,24 +1,56 @@
 extern crate gphoto2_sys as gphoto2;
 extern crate libc;
 
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+
+#[cfg(feature = "json")]
+extern crate serde_json;
+
+#[cfg(feature = "toml")]
+extern crate toml;
+
 pub use error::{Result,Error,ErrorKind};
-pub use abilities::{Abilities,DeviceType,DriverStatus,CameraOperation,FileOperation,FolderOperation};
-pub use camera::{Camera,CameraFile};
+pub use abilities::{Abilities,AbilitiesList,AbilitiesIter,DeviceType,DriverStatus,CameraOperation,FileOperation,FolderOperation};
+pub use camera::{Camera,CameraBuilder,CameraFile,CameraEvent,DetectedCamera};
 pub use context::{Context};
+pub use exposure::{ExposureSetting,ShutterSpeed,Aperture,Iso,ExposureCompensation,ParseExposureError};
 pub use media::{Media,FileMedia};
-pub use port::{PortType,Port};
+pub use monitor::{CameraMonitor,MonitorEvent};
+pub use names::{CommonSetting,SettingNames};
+pub use port::{PortType,Port,PortInfoList,PortIter};
+pub use preset::{Preset};
+pub use schema::{WidgetSchema};
+pub use snapshot::{ConfigSnapshot,ApplyOutcome,ConfigDiff,SettingChange};
 pub use storage::{Storage,StorageType,FilesystemType,AccessType};
+pub use transaction::{ConfigTransaction};
 pub use version::{LibraryVersion,libgphoto2_version};
+pub use widget::{Widget,WidgetType,WidgetRange,SettingValue};
+pub use watcher::{ConfigWatcher,SettingChanged};
+pub use worker::{CameraHandle};
 
 #[macro_use]
 mod error;
 mod abilities;
 mod camera;
 mod context;
+mod exposure;
 mod media;
+mod monitor;
+mod names;
 mod port;
+mod preset;
+mod schema;
+mod snapshot;
 mod storage;
+mod transaction;
 mod version;
+mod watcher;
+mod widget;
+mod worker;
 
 // internal
+mod ffi;
 mod handle;
+mod list;
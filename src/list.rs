@@ -0,0 +1,70 @@
+use std::ffi::CStr;
+use std::mem;
+
+use ::libc::c_int;
+
+use ::handle::{Handle,HandleMut};
+
+/// A list of name/value pairs returned by `libgphoto2`.
+pub struct CameraList {
+    list: *mut ::gphoto2::CameraList,
+}
+
+impl Drop for CameraList {
+    fn drop(&mut self) {
+        unsafe {
+            ::gphoto2::gp_list_unref(self.list);
+        }
+    }
+}
+
+impl CameraList {
+    /// Creates a new, empty list.
+    pub fn new() -> ::Result<Self> {
+        let mut list = mem::MaybeUninit::uninit();
+
+        try_unsafe!(::gphoto2::gp_list_new(list.as_mut_ptr()));
+
+        Ok(CameraList { list: unsafe { list.assume_init() } })
+    }
+
+    /// Returns the number of entries in the list.
+    pub fn count(&self) -> usize {
+        match unsafe { ::gphoto2::gp_list_count(self.list) } {
+            n if n > 0 => n as usize,
+            _ => 0,
+        }
+    }
+
+    /// Returns the name of the entry at `index`.
+    pub fn name(&self, index: usize) -> String {
+        let mut name = mem::MaybeUninit::uninit();
+
+        unsafe {
+            assert_eq!(::gphoto2::GP_OK, ::gphoto2::gp_list_get_name(self.list, index as c_int, name.as_mut_ptr()));
+            String::from_utf8_lossy(CStr::from_ptr(name.assume_init()).to_bytes()).into_owned()
+        }
+    }
+
+    /// Returns the value of the entry at `index`.
+    pub fn value(&self, index: usize) -> String {
+        let mut value = mem::MaybeUninit::uninit();
+
+        unsafe {
+            assert_eq!(::gphoto2::GP_OK, ::gphoto2::gp_list_get_value(self.list, index as c_int, value.as_mut_ptr()));
+            String::from_utf8_lossy(CStr::from_ptr(value.assume_init()).to_bytes()).into_owned()
+        }
+    }
+}
+
+impl Handle<::gphoto2::CameraList> for CameraList {
+    unsafe fn as_ptr(&self) -> *const ::gphoto2::CameraList {
+        self.list
+    }
+}
+
+impl HandleMut<::gphoto2::CameraList> for CameraList {
+    unsafe fn as_mut_ptr(&mut self) -> *mut ::gphoto2::CameraList {
+        self.list
+    }
+}
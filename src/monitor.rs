@@ -0,0 +1,153 @@
+use std::sync::mpsc::{self,Receiver,Sender,RecvTimeoutError};
+use std::thread::{self,JoinHandle};
+use std::time::Duration;
+
+use ::camera::{Camera,DetectedCamera};
+use ::context::Context;
+
+/// Events reported by a `CameraMonitor`.
+#[derive(Debug,PartialEq,Eq,Clone,Hash)]
+pub enum MonitorEvent {
+    /// A camera was connected.
+    Connected(DetectedCamera),
+
+    /// A camera was disconnected.
+    Disconnected(DetectedCamera),
+}
+
+/// Monitors the system for cameras being connected and disconnected.
+///
+/// A `CameraMonitor` detects cameras on a background thread at a fixed interval and reports the
+/// differences between consecutive detections as events. A camera is identified by its model and
+/// port path. Cameras that are already connected when the monitor is started are reported as
+/// `Connected` events.
+///
+/// The background thread is stopped when the monitor is dropped.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// let monitor = gphoto::CameraMonitor::new(Duration::from_secs(1)).unwrap();
+///
+/// while let Some(event) = monitor.recv() {
+///     match event {
+///         gphoto::MonitorEvent::Connected(camera) => {
+///             println!("connected: {} on {}", camera.model(), camera.port_path());
+///         },
+///         gphoto::MonitorEvent::Disconnected(camera) => {
+///             println!("disconnected: {} on {}", camera.model(), camera.port_path());
+///         },
+///     }
+/// }
+/// ```
+pub struct CameraMonitor {
+    events: Receiver<MonitorEvent>,
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl CameraMonitor {
+    /// Starts monitoring, detecting cameras every `interval`.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the `libgphoto2` context for the background thread could
+    /// not be created.
+    pub fn new(interval: Duration) -> ::Result<Self> {
+        let (event_tx, event_rx) = mpsc::channel();
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let (ready_tx, ready_rx) = mpsc::channel();
+
+        let thread = thread::spawn(move || {
+            let mut context = match Context::new() {
+                Ok(c) => {
+                    let _ = ready_tx.send(Ok(()));
+                    c
+                },
+                Err(err) => {
+                    let _ = ready_tx.send(Err(err));
+                    return;
+                },
+            };
+
+            let mut known = Vec::new();
+
+            loop {
+                // Detection fails transiently while devices are being enumerated, so a failed
+                // detection is skipped rather than reported as every camera disconnecting.
+                if let Ok(detected) = Camera::detect_all(&mut context) {
+                    for event in diff(&known, &detected) {
+                        if event_tx.send(event).is_err() {
+                            return;
+                        }
+                    }
+
+                    known = detected;
+                }
+
+                match stop_rx.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    _ => return,
+                }
+            }
+        });
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => (),
+            Ok(Err(err)) => return Err(err),
+            Err(_) => return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR)),
+        }
+
+        Ok(CameraMonitor {
+            events: event_rx,
+            stop: Some(stop_tx),
+            thread: Some(thread),
+        })
+    }
+
+    /// Waits for the next event.
+    ///
+    /// Returns `None` if the background thread has stopped.
+    pub fn recv(&self) -> Option<MonitorEvent> {
+        self.events.recv().ok()
+    }
+
+    /// Returns the next event if one is available without waiting.
+    pub fn try_recv(&self) -> Option<MonitorEvent> {
+        self.events.try_recv().ok()
+    }
+
+    /// Waits up to `timeout` for the next event.
+    ///
+    /// Returns `None` if no event occurred before the timeout or the background thread has
+    /// stopped.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<MonitorEvent> {
+        self.events.recv_timeout(timeout).ok()
+    }
+}
+
+impl Drop for CameraMonitor {
+    fn drop(&mut self) {
+        // Disconnecting the stop channel wakes the background thread.
+        drop(self.stop.take());
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Computes the events that turn the `previous` set of cameras into the `current` set.
+fn diff(previous: &[DetectedCamera], current: &[DetectedCamera]) -> Vec<MonitorEvent> {
+    let disconnected = previous.iter()
+        .filter(|camera| !current.contains(camera))
+        .map(|camera| MonitorEvent::Disconnected(camera.clone()));
+
+    let connected = current.iter()
+        .filter(|camera| !previous.contains(camera))
+        .map(|camera| MonitorEvent::Connected(camera.clone()));
+
+    disconnected.chain(connected).collect()
+}
@@ -0,0 +1,186 @@
+use ::camera::Camera;
+use ::context::Context;
+use ::widget::Widget;
+
+/// Settings that most cameras have under different names.
+///
+/// Drivers name the widgets for the same concept differently depending on the camera's vendor and
+/// model, e.g., the shutter speed of Nikon cameras is settable through `shutterspeed2` rather than
+/// `shutterspeed`. A `CommonSetting` identifies such a concept independently of the camera, and
+/// `SettingNames` resolves it to the widget name of a specific camera.
+#[derive(Debug,PartialEq,Eq,Clone,Copy,Hash)]
+pub enum CommonSetting {
+    /// The ISO sensitivity.
+    Iso,
+
+    /// The aperture.
+    Aperture,
+
+    /// The shutter speed.
+    ShutterSpeed,
+
+    /// The white balance.
+    WhiteBalance,
+
+    /// The image format or quality, e.g., RAW or JPEG.
+    ImageFormat,
+
+    /// The focus mode.
+    FocusMode,
+
+    /// The storage that captured images are saved to.
+    CaptureTarget,
+
+    /// The battery level.
+    BatteryLevel,
+}
+
+/// Camera vendors whose drivers use their own widget names.
+#[derive(Debug,PartialEq,Eq,Clone,Copy)]
+enum Vendor {
+    Canon,
+    Nikon,
+    Sony,
+    Other,
+}
+
+impl Vendor {
+    fn from_model(model: &str) -> Self {
+        let model = model.to_lowercase();
+
+        if model.starts_with("canon") {
+            Vendor::Canon
+        }
+        else if model.starts_with("nikon") {
+            Vendor::Nikon
+        }
+        else if model.starts_with("sony") {
+            Vendor::Sony
+        }
+        else {
+            Vendor::Other
+        }
+    }
+}
+
+impl CommonSetting {
+    /// Returns all common settings.
+    pub fn all() -> &'static [CommonSetting] {
+        const ALL: [CommonSetting; 8] = [
+            CommonSetting::Iso,
+            CommonSetting::Aperture,
+            CommonSetting::ShutterSpeed,
+            CommonSetting::WhiteBalance,
+            CommonSetting::ImageFormat,
+            CommonSetting::FocusMode,
+            CommonSetting::CaptureTarget,
+            CommonSetting::BatteryLevel,
+        ];
+
+        &ALL
+    }
+
+    /// Returns the widget names that may hold the setting on a camera model, in order of preference.
+    ///
+    /// `model` is a model name as returned by `Abilities::model()`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use gphoto::CommonSetting;
+    ///
+    /// assert_eq!(&["shutterspeed2", "shutterspeed"],
+    ///            CommonSetting::ShutterSpeed.candidates("Nikon DSC D750"));
+    ///
+    /// assert_eq!(&["shutterspeed"],
+    ///            CommonSetting::ShutterSpeed.candidates("Canon EOS 5D Mark III"));
+    /// ```
+    pub fn candidates(&self, model: &str) -> &'static [&'static str] {
+        match (*self, Vendor::from_model(model)) {
+            (CommonSetting::Iso, _) => &["iso", "isospeed"],
+
+            (CommonSetting::Aperture, Vendor::Canon) => &["aperture", "f-number"],
+            (CommonSetting::Aperture, _) => &["f-number", "aperture"],
+
+            (CommonSetting::ShutterSpeed, Vendor::Nikon) => &["shutterspeed2", "shutterspeed"],
+            (CommonSetting::ShutterSpeed, _) => &["shutterspeed"],
+
+            (CommonSetting::WhiteBalance, _) => &["whitebalance"],
+
+            (CommonSetting::ImageFormat, Vendor::Nikon)
+            | (CommonSetting::ImageFormat, Vendor::Sony) => &["imagequality", "imageformat"],
+            (CommonSetting::ImageFormat, _) => &["imageformat", "imagequality"],
+
+            (CommonSetting::FocusMode, Vendor::Nikon) => &["focusmode2", "focusmode"],
+            (CommonSetting::FocusMode, _) => &["focusmode"],
+
+            (CommonSetting::CaptureTarget, _) => &["capturetarget"],
+
+            (CommonSetting::BatteryLevel, _) => &["batterylevel"],
+        }
+    }
+
+    /// Finds the name of the widget that holds the setting in a configuration tree.
+    ///
+    /// Returns the first of the setting's candidates for `model` that the tree contains, or `None`
+    /// if the tree contains none of them.
+    pub fn resolve(&self, model: &str, config: &Widget) -> Option<&'static str> {
+        self.candidates(model).iter()
+            .find(|name| config.child_by_name(name).is_some())
+            .cloned()
+    }
+}
+
+/// The widget names of the common settings of a camera.
+///
+/// ## Example
+///
+/// ```no_run
+/// use gphoto::CommonSetting;
+///
+/// let mut context = gphoto::Context::new().unwrap();
+/// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+///
+/// let names = gphoto::SettingNames::from_camera(&mut camera, &mut context).unwrap();
+///
+/// if let Some(name) = names.get(CommonSetting::ShutterSpeed) {
+///     println!("shutter speed = {:?}", camera.get_setting(&mut context, name).unwrap());
+/// }
+/// ```
+#[derive(Debug,Clone)]
+pub struct SettingNames {
+    names: Vec<(CommonSetting, &'static str)>,
+}
+
+impl SettingNames {
+    /// Resolves the common settings of a camera model in a configuration tree.
+    ///
+    /// See `CommonSetting::resolve()`.
+    pub fn resolve(model: &str, config: &Widget) -> Self {
+        let names = CommonSetting::all().iter().filter_map(|setting| {
+            setting.resolve(model, config).map(|name| (*setting, name))
+        }).collect();
+
+        SettingNames { names }
+    }
+
+    /// Resolves the common settings of a camera.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera's configuration could not be retrieved:
+    ///
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn from_camera(camera: &mut Camera, context: &mut Context) -> ::Result<Self> {
+        let config = camera.config(context)?;
+
+        Ok(SettingNames::resolve(&camera.abilities().model(), &config))
+    }
+
+    /// Returns the widget name of a common setting.
+    ///
+    /// Returns `None` if the camera does not have the setting.
+    pub fn get(&self, setting: CommonSetting) -> Option<&'static str> {
+        self.names.iter().find(|&&(s, _)| s == setting).map(|&(_, name)| name)
+    }
+}
@@ -1,9 +1,11 @@
 use std::borrow::Cow;
-use std::ffi::CStr;
+use std::ffi::{CStr,CString};
 use std::marker::PhantomData;
 use std::mem;
 
-use ::libc::c_void;
+use ::libc::{c_int,c_void};
+
+use ::handle::prelude::*;
 
 /// Types of ports.
 #[derive(Debug,PartialEq,Eq,Clone,Copy,Hash)]
@@ -55,7 +57,7 @@ pub enum PortType {
 /// ```
 pub struct Port<'a> {
     // GPPortInfo is a typedef for a pointer. Lifetime is needed because it borrows data owned by
-    // the Camera struct.
+    // the Camera or PortInfoList struct.
     inner: ::gphoto2::GPPortInfo,
     __phantom: PhantomData<&'a c_void>,
 }
@@ -101,6 +103,151 @@ impl<'a> Port<'a> {
     }
 }
 
+#[doc(hidden)]
+impl<'a> Handle<::gphoto2::_GPPortInfo> for Port<'a> {
+    unsafe fn as_ptr(&self) -> *const ::gphoto2::_GPPortInfo {
+        self.inner
+    }
+}
+
+/// A list of the ports available to `libgphoto2`.
+///
+/// ## Example
+///
+/// A `PortInfoList` can be used to list the connection points that `libgphoto2` can see, which
+/// helps to diagnose why a camera is not detected:
+///
+/// ```no_run
+/// let ports = gphoto::PortInfoList::new().unwrap();
+///
+/// for port in &ports {
+///     println!("{:?} {:?} {:?}", port.port_type(), port.name(), port.path());
+/// }
+/// ```
+///
+/// The above example may print something like the following:
+///
+/// ```text
+/// Serial "Serial Port 0" "serial:/dev/ttyS0"
+/// USB "Universal Serial Bus" "usb:001,007"
+/// Disk "Media '/media/card'" "disk:/media/card"
+/// PTPIP "" "ptpip:"
+/// ```
+pub struct PortInfoList {
+    list: *mut ::gphoto2::GPPortInfoList,
+}
+
+impl Drop for PortInfoList {
+    fn drop(&mut self) {
+        unsafe {
+            ::gphoto2::gp_port_info_list_free(self.list);
+        }
+    }
+}
+
+impl PortInfoList {
+    /// Loads the list of ports available on the system.
+    pub fn new() -> ::Result<Self> {
+        let mut list = mem::MaybeUninit::uninit();
+
+        try_unsafe!(::gphoto2::gp_port_info_list_new(list.as_mut_ptr()));
+
+        let list = PortInfoList { list: unsafe { list.assume_init() } as *mut _ };
+
+        try_unsafe!(::gphoto2::gp_port_info_list_load(list.list));
+
+        Ok(list)
+    }
+
+    /// Returns the number of ports in the list.
+    pub fn len(&self) -> usize {
+        match unsafe { ::gphoto2::gp_port_info_list_count(self.list) } {
+            n if n > 0 => n as usize,
+            _ => 0,
+        }
+    }
+
+    /// Returns `true` if the list contains no ports.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the port at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Port<'_>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let mut info = mem::MaybeUninit::uninit();
+
+        match unsafe { ::gphoto2::gp_port_info_list_get_info(self.list, index as c_int, info.as_mut_ptr()) } {
+            ::gphoto2::GP_OK => Some(Port { inner: unsafe { info.assume_init() }, __phantom: PhantomData }),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the ports in the list.
+    pub fn iter(&self) -> PortIter<'_> {
+        PortIter {
+            list: self,
+            index: 0,
+        }
+    }
+
+    /// Looks up a port by its path, e.g., `"usb:001,007"`.
+    ///
+    /// Paths that are matched by a generic port, such as `"disk:/media/card"` or
+    /// `"ptpip:192.168.1.20"`, are resolved to a port with that exact path. Returns `None` if no
+    /// port matches the path.
+    pub fn find_path(&self, path: &str) -> Option<Port<'_>> {
+        let path = match CString::new(path) {
+            Ok(s) => s,
+            Err(_) => return None,
+        };
+
+        match unsafe { ::gphoto2::gp_port_info_list_lookup_path(self.list, path.as_ptr()) } {
+            index if index >= 0 => self.get(index as usize),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a PortInfoList {
+    type Item = Port<'a>;
+    type IntoIter = PortIter<'a>;
+
+    fn into_iter(self) -> PortIter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the ports in a `PortInfoList`.
+pub struct PortIter<'a> {
+    list: &'a PortInfoList,
+    index: usize,
+}
+
+impl<'a> Iterator for PortIter<'a> {
+    type Item = Port<'a>;
+
+    fn next(&mut self) -> Option<Port<'a>> {
+        let port = self.list.get(self.index);
+
+        if port.is_some() {
+            self.index += 1;
+        }
+
+        port
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.list.len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
 #[doc(hidden)]
 pub fn from_libgphoto2(_camera: & ::camera::Camera, ptr: ::gphoto2::GPPortInfo) -> Port {
     Port {
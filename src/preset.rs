@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use ::widget::SettingValue;
+
+/// A named set of settings that can be applied to cameras of different models.
+///
+/// A preset contains settings that apply to every camera and overrides for specific models. The
+/// overrides for a model replace or add to the common settings when the preset is applied to a
+/// camera of that model with `Camera::apply_preset()`. Models are identified by the model names
+/// returned by `Abilities::model()`, compared ignoring case.
+///
+/// Presets are typically stored as partial configuration files. With the `serde` feature, presets
+/// implement `Serialize` and `Deserialize`, and the `json` and `toml` features add functions to
+/// convert presets to and from those formats.
+///
+/// ## Example
+///
+/// ```
+/// use gphoto::{Preset,SettingValue};
+///
+/// let mut preset = Preset::new("studio strobe");
+///
+/// preset.settings.insert("iso".to_owned(), SettingValue::Choice("100".to_owned()));
+/// preset.settings.insert("shutterspeed".to_owned(), SettingValue::Choice("1/125".to_owned()));
+///
+/// preset.set_override("Nikon DSC D750", "iso", SettingValue::Choice("Lo 1.0".to_owned()));
+///
+/// let settings = preset.settings_for("Nikon DSC D750");
+/// assert_eq!(Some(&SettingValue::Choice("Lo 1.0".to_owned())), settings.get("iso"));
+///
+/// let settings = preset.settings_for("Canon EOS 5D Mark III");
+/// assert_eq!(Some(&SettingValue::Choice("100".to_owned())), settings.get("iso"));
+/// ```
+///
+/// As TOML, the above preset looks like the following:
+///
+/// ```text
+/// name = "studio strobe"
+///
+/// [settings]
+/// iso = { choice = "100" }
+/// shutterspeed = { choice = "1/125" }
+///
+/// [models."Nikon DSC D750"]
+/// iso = { choice = "Lo 1.0" }
+/// ```
+#[derive(Debug,PartialEq,Clone,Default)]
+#[cfg_attr(feature = "serde", derive(Serialize,Deserialize))]
+pub struct Preset {
+    /// The preset's name, e.g., `"outdoor daylight"`.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub name: Option<String>,
+
+    /// The settings for every camera by name or path.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub settings: BTreeMap<String, SettingValue>,
+
+    /// The overriding settings for specific models, by model name and setting name.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeMap::is_empty"))]
+    pub models: BTreeMap<String, BTreeMap<String, SettingValue>>,
+}
+
+impl Preset {
+    /// Creates an empty preset with a name.
+    pub fn new(name: &str) -> Self {
+        Preset {
+            name: Some(name.to_owned()),
+            ..Preset::default()
+        }
+    }
+
+    /// Adds an overriding setting for a model.
+    pub fn set_override(&mut self, model: &str, name: &str, value: SettingValue) {
+        self.models.entry(model.to_owned()).or_default().insert(name.to_owned(), value);
+    }
+
+    /// Returns the settings to apply to a camera of the given model.
+    ///
+    /// The result contains the common settings, replaced or extended by the overrides for `model`.
+    pub fn settings_for(&self, model: &str) -> BTreeMap<String, SettingValue> {
+        let mut settings = self.settings.clone();
+
+        for (preset_model, overrides) in &self.models {
+            if preset_model.eq_ignore_ascii_case(model) {
+                settings.extend(overrides.iter().map(|(name, value)| (name.clone(), value.clone())));
+            }
+        }
+
+        settings
+    }
+
+    /// Serializes the preset as JSON.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> ::serde_json::Result<String> {
+        ::serde_json::to_string_pretty(self)
+    }
+
+    /// Deserializes a preset from JSON.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> ::serde_json::Result<Self> {
+        ::serde_json::from_str(json)
+    }
+
+    /// Serializes the preset as TOML.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String, ::toml::ser::Error> {
+        ::toml::to_string(self)
+    }
+
+    /// Deserializes a preset from TOML.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, ::toml::de::Error> {
+        ::toml::from_str(toml)
+    }
+}
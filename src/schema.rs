@@ -0,0 +1,123 @@
+use ::camera::Camera;
+use ::context::Context;
+use ::widget::{Widget,WidgetType,WidgetRange,SettingValue};
+
+/// A description of a widget and its descendants.
+///
+/// A schema describes everything that is needed to present a camera's settings without knowing the
+/// camera's model in advance: each widget's type, label, help text, whether it can be changed, and
+/// its choices or range. With the `serde` feature, schemas implement `Serialize` and
+/// `Deserialize`; the `json` feature adds `to_json()`.
+///
+/// ## Example
+///
+/// ```no_run
+/// let mut context = gphoto::Context::new().unwrap();
+/// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+///
+/// let schema = gphoto::WidgetSchema::from_camera(&mut camera, &mut context).unwrap();
+///
+/// for section in &schema.children {
+///     println!("{}", section.label);
+///
+///     for setting in &section.children {
+///         println!("    {} ({:?}): {:?}", setting.label, setting.widget_type, setting.value);
+///     }
+/// }
+/// ```
+///
+/// Serialized as JSON, a setting's schema looks like the following:
+///
+/// ```text
+/// {
+///   "name": "iso",
+///   "path": "/main/imgsettings/iso",
+///   "label": "ISO Speed",
+///   "info": "",
+///   "type": "radio",
+///   "readonly": false,
+///   "choices": ["Auto", "100", "200", "400"],
+///   "value": { "choice": "100" }
+/// }
+/// ```
+#[derive(Debug,PartialEq,Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize,Deserialize))]
+pub struct WidgetSchema {
+    /// The widget's name.
+    pub name: String,
+
+    /// The widget's path (see `Widget::path()`).
+    pub path: String,
+
+    /// The widget's human-readable label.
+    pub label: String,
+
+    /// The widget's help text, which is often empty.
+    pub info: String,
+
+    /// The type of the widget.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub widget_type: WidgetType,
+
+    /// Whether the widget's value can not be changed.
+    pub readonly: bool,
+
+    /// The choices of a `Radio` or `Menu` widget.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub choices: Vec<String>,
+
+    /// The bounds of a `Range` widget.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub range: Option<WidgetRange>,
+
+    /// The widget's current value.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub value: Option<SettingValue>,
+
+    /// The schemas of the widget's children.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub children: Vec<WidgetSchema>,
+}
+
+impl WidgetSchema {
+    /// Describes a widget and its descendants.
+    pub fn from_widget(widget: &Widget) -> Self {
+        WidgetSchema::describe(widget, widget.path())
+    }
+
+    /// Describes a camera's configuration tree.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera's configuration could not be retrieved:
+    ///
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn from_camera(camera: &mut Camera, context: &mut Context) -> ::Result<Self> {
+        Ok(WidgetSchema::from_widget(&camera.config(context)?))
+    }
+
+    /// Serializes the schema as JSON.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> ::serde_json::Result<String> {
+        ::serde_json::to_string_pretty(self)
+    }
+
+    fn describe(widget: &Widget, path: String) -> Self {
+        let children = widget.children().iter().map(|child| {
+            WidgetSchema::describe(child, format!("{}/{}", path, child.name()))
+        }).collect();
+
+        WidgetSchema {
+            name: widget.name().into_owned(),
+            path,
+            label: widget.label().into_owned(),
+            info: widget.info().into_owned(),
+            widget_type: widget.widget_type(),
+            readonly: widget.readonly(),
+            choices: widget.choices(),
+            range: widget.range(),
+            value: widget.value(),
+            children,
+        }
+    }
+}
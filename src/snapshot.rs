@@ -0,0 +1,336 @@
+use std::collections::BTreeMap;
+
+use ::camera::Camera;
+use ::context::Context;
+use ::widget::{Widget,WidgetType,SettingValue};
+
+/// A saved copy of a camera's configuration.
+///
+/// A snapshot records the value of every setting in a camera's configuration tree by name. It can
+/// be written back to the same or another camera with `apply()`, which makes it possible to return
+/// a camera to a known state.
+///
+/// With the `serde` feature, snapshots implement `Serialize` and `Deserialize`. The `json` and
+/// `toml` features add functions to convert snapshots to and from those formats.
+///
+/// ## Example
+///
+/// ```no_run
+/// let mut context = gphoto::Context::new().unwrap();
+/// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+///
+/// let snapshot = gphoto::ConfigSnapshot::from_camera(&mut camera, &mut context).unwrap();
+///
+/// // ...
+///
+/// for (name, outcome) in snapshot.apply(&mut camera, &mut context).unwrap() {
+///     println!("{}: {:?}", name, outcome);
+/// }
+/// ```
+#[derive(Debug,PartialEq,Clone,Default)]
+#[cfg_attr(feature = "serde", derive(Serialize,Deserialize))]
+pub struct ConfigSnapshot {
+    /// The model of the camera that the snapshot was taken from.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub model: Option<String>,
+
+    /// The values of the camera's settings by name.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub settings: BTreeMap<String, SettingValue>,
+}
+
+/// The result of applying a single setting from a `ConfigSnapshot`.
+#[derive(Debug)]
+pub enum ApplyOutcome {
+    /// The setting was changed to the saved value.
+    Applied,
+
+    /// The setting already had the saved value.
+    Unchanged,
+
+    /// The setting has a different value, but it can not be changed.
+    ReadOnly,
+
+    /// The saved value is not one of the setting's choices on this camera.
+    ChoiceUnavailable,
+
+    /// The camera does not have a setting with this name.
+    Missing,
+
+    /// The setting is a date, such as the camera's clock, which is not restored.
+    Skipped,
+
+    /// The camera rejected the saved value.
+    Failed(::Error),
+}
+
+/// The differences between two camera configurations.
+///
+/// The old configuration is the one that `diff()` was called on, and the new configuration is the
+/// argument to `diff()`.
+///
+/// ## Example
+///
+/// ```
+/// use gphoto::{ConfigSnapshot,SettingValue};
+///
+/// let mut old = ConfigSnapshot::default();
+/// old.settings.insert("iso".to_owned(), SettingValue::Choice("100".to_owned()));
+/// old.settings.insert("whitebalance".to_owned(), SettingValue::Choice("Daylight".to_owned()));
+///
+/// let mut new = old.clone();
+/// new.settings.insert("iso".to_owned(), SettingValue::Choice("400".to_owned()));
+/// new.settings.remove("whitebalance");
+///
+/// let diff = old.diff(&new);
+///
+/// assert!(diff.added.is_empty());
+/// assert!(diff.removed.contains_key("whitebalance"));
+/// assert_eq!(SettingValue::Choice("400".to_owned()), diff.changed["iso"].new);
+/// ```
+#[derive(Debug,PartialEq,Clone,Default)]
+#[cfg_attr(feature = "serde", derive(Serialize,Deserialize))]
+pub struct ConfigDiff {
+    /// Settings that only the new configuration has, with their values.
+    pub added: BTreeMap<String, SettingValue>,
+
+    /// Settings that only the old configuration has, with their values.
+    pub removed: BTreeMap<String, SettingValue>,
+
+    /// Settings that have different values in both configurations.
+    pub changed: BTreeMap<String, SettingChange>,
+}
+
+/// A setting whose value differs between two configurations.
+#[derive(Debug,PartialEq,Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize,Deserialize))]
+pub struct SettingChange {
+    /// The value in the old configuration.
+    pub old: SettingValue,
+
+    /// The value in the new configuration.
+    pub new: SettingValue,
+}
+
+impl ConfigDiff {
+    /// Compares the configurations of two cameras.
+    ///
+    /// Both configurations are retrieved from the cameras and compared as snapshots. Settings that
+    /// change on their own, such as `datetime` or `batterylevel`, are included in the result.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    ///
+    /// let mut left = gphoto::Camera::open(&mut context, "Nikon DSC D750", "usb:001,007").unwrap();
+    /// let mut right = gphoto::Camera::open(&mut context, "Nikon DSC D750", "usb:001,008").unwrap();
+    ///
+    /// let diff = gphoto::ConfigDiff::between(&mut left, &mut right, &mut context).unwrap();
+    ///
+    /// for (name, change) in diff.changed {
+    ///     println!("{}: {:?} -> {:?}", name, change.old, change.new);
+    /// }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if either camera's configuration could not be retrieved:
+    ///
+    /// * `NotSupported` if a camera can not be configured.
+    pub fn between(old: &mut Camera, new: &mut Camera, context: &mut Context) -> ::Result<Self> {
+        let old = ConfigSnapshot::from_camera(old, context)?;
+        let new = ConfigSnapshot::from_camera(new, context)?;
+
+        Ok(old.diff(&new))
+    }
+
+    /// Returns `true` if the configurations are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl ConfigSnapshot {
+    /// Records the values of all settings in a configuration tree.
+    ///
+    /// Widgets without a value, such as sections and buttons, are skipped. If several widgets have
+    /// the same name, the first one is recorded, which is the one that `Widget::child_by_name()`
+    /// finds.
+    pub fn from_widget(root: &Widget) -> Self {
+        let mut settings = BTreeMap::new();
+
+        collect(root, &mut settings);
+
+        ConfigSnapshot {
+            model: None,
+            settings,
+        }
+    }
+
+    /// Records the values of all of a camera's settings and the camera's model.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera's configuration could not be retrieved:
+    ///
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn from_camera(camera: &mut Camera, context: &mut Context) -> ::Result<Self> {
+        let mut snapshot = ConfigSnapshot::from_widget(&camera.config(context)?);
+
+        snapshot.model = Some(camera.abilities().model().into_owned());
+
+        Ok(snapshot)
+    }
+
+    /// Writes the saved settings to a camera.
+    ///
+    /// The camera's configuration is retrieved once and compared to the snapshot. Each setting that
+    /// differs from the saved value is written to the camera individually, so a rejected value does
+    /// not prevent the remaining settings from being applied. The snapshot's model is not checked,
+    /// so a snapshot can be applied to a different model; settings that the camera does not have
+    /// are reported as `Missing`. Date settings, such as the camera's clock, are not written, so
+    /// that applying a snapshot does not set the clock back; they are reported as `Skipped`.
+    ///
+    /// Settings may also be identified by their paths, e.g., `"/main/imgsettings/iso"` (see
+    /// `Widget::child_by_path()`). For each of them, the configuration is retrieved again and
+    /// written as a whole, so that the value is not applied to another widget with the same name.
+    ///
+    /// Returns the outcome for each setting in the snapshot.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera's configuration could not be retrieved:
+    ///
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn apply(&self, camera: &mut Camera, context: &mut Context) -> ::Result<BTreeMap<String, ApplyOutcome>> {
+        let root = camera.config(context)?;
+
+        let outcomes = self.settings.iter().map(|(name, value)| {
+            let outcome = if name.starts_with('/') {
+                apply_path(camera, context, name, value)
+            }
+            else {
+                match root.child_by_name(name) {
+                    Some(mut widget) => apply_setting(camera, context, &mut widget, None, value),
+                    None => ApplyOutcome::Missing,
+                }
+            };
+
+            (name.clone(), outcome)
+        }).collect();
+
+        Ok(outcomes)
+    }
+
+    /// Compares the snapshot's settings with those of a newer snapshot.
+    ///
+    /// The snapshots' models are not compared.
+    pub fn diff(&self, new: &ConfigSnapshot) -> ConfigDiff {
+        let mut diff = ConfigDiff::default();
+
+        for (name, old_value) in &self.settings {
+            match new.settings.get(name) {
+                Some(new_value) if new_value == old_value => (),
+                Some(new_value) => {
+                    diff.changed.insert(name.clone(), SettingChange {
+                        old: old_value.clone(),
+                        new: new_value.clone(),
+                    });
+                },
+                None => {
+                    diff.removed.insert(name.clone(), old_value.clone());
+                },
+            }
+        }
+
+        for (name, new_value) in &new.settings {
+            if !self.settings.contains_key(name) {
+                diff.added.insert(name.clone(), new_value.clone());
+            }
+        }
+
+        diff
+    }
+
+    /// Serializes the snapshot as JSON.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> ::serde_json::Result<String> {
+        ::serde_json::to_string_pretty(self)
+    }
+
+    /// Deserializes a snapshot from JSON.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> ::serde_json::Result<Self> {
+        ::serde_json::from_str(json)
+    }
+
+    /// Serializes the snapshot as TOML.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String, ::toml::ser::Error> {
+        ::toml::to_string(self)
+    }
+
+    /// Deserializes a snapshot from TOML.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, ::toml::de::Error> {
+        ::toml::from_str(toml)
+    }
+}
+
+fn collect(widget: &Widget, settings: &mut BTreeMap<String, SettingValue>) {
+    if let Some(value) = widget.value() {
+        settings.entry(widget.name().into_owned()).or_insert(value);
+    }
+
+    for child in widget.children() {
+        collect(&child, settings);
+    }
+}
+
+// A setting identified by its path is written with the full configuration tree, because
+// `Camera::set_config_widget()` finds widgets by name and could write a different widget with the
+// same name. Each path gets a new tree, so that only its own change is written.
+fn apply_path(camera: &mut Camera, context: &mut Context, path: &str, value: &SettingValue) -> ApplyOutcome {
+    let mut root = match camera.config(context) {
+        Ok(root) => root,
+        Err(err) => return ApplyOutcome::Failed(err),
+    };
+
+    match root.child_by_path(path) {
+        Some(mut widget) => apply_setting(camera, context, &mut widget, Some(&mut root), value),
+        None => ApplyOutcome::Missing,
+    }
+}
+
+fn apply_setting(camera: &mut Camera, context: &mut Context, widget: &mut Widget, root: Option<&mut Widget>, value: &SettingValue) -> ApplyOutcome {
+    if widget.widget_type() == WidgetType::Date {
+        return ApplyOutcome::Skipped;
+    }
+
+    if widget.value().as_ref() == Some(value) {
+        return ApplyOutcome::Unchanged;
+    }
+
+    if widget.readonly() {
+        return ApplyOutcome::ReadOnly;
+    }
+
+    if let SettingValue::Choice(ref choice) = *value {
+        let has_choices = matches!(widget.widget_type(), WidgetType::Radio | WidgetType::Menu);
+
+        if has_choices && !widget.choices().contains(choice) {
+            return ApplyOutcome::ChoiceUnavailable;
+        }
+    }
+
+    let result = widget.set_value(value.clone()).and_then(|_| match root {
+        Some(root) => camera.set_config(context, root),
+        None => camera.set_config_widget(context, widget),
+    });
+
+    match result {
+        Ok(()) => ApplyOutcome::Applied,
+        Err(err) => ApplyOutcome::Failed(err),
+    }
+}
@@ -0,0 +1,132 @@
+use ::camera::Camera;
+use ::context::Context;
+use ::widget::{Widget,SettingValue};
+
+/// A set of configuration changes that are written to a camera together.
+///
+/// A transaction retrieves the camera's configuration tree once and stages changes on it. When the
+/// transaction is committed, all staged changes are written to the camera with a single request. If
+/// the camera rejects the changes, the staged settings are returned to their previous values so
+/// that the camera is not left partially configured.
+///
+/// Dropping a transaction without committing it discards the staged changes.
+///
+/// ## Example
+///
+/// ```no_run
+/// use gphoto::SettingValue;
+///
+/// let mut context = gphoto::Context::new().unwrap();
+/// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+///
+/// let mut transaction = gphoto::ConfigTransaction::new(&mut camera, &mut context).unwrap();
+///
+/// transaction.set("aperture", SettingValue::Choice("5.6".to_owned())).unwrap();
+/// transaction.set("shutterspeed", SettingValue::Choice("1/250".to_owned())).unwrap();
+/// transaction.set("iso", SettingValue::Choice("400".to_owned())).unwrap();
+///
+/// transaction.commit(&mut context).unwrap();
+/// ```
+pub struct ConfigTransaction<'a> {
+    camera: &'a mut Camera,
+    root: Widget,
+
+    // Each staged widget with the value it had before it was first staged.
+    staged: Vec<(Widget, SettingValue)>,
+}
+
+impl<'a> ConfigTransaction<'a> {
+    /// Starts a transaction by retrieving the camera's configuration.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera's configuration could not be retrieved:
+    ///
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn new(camera: &'a mut Camera, context: &mut Context) -> ::Result<Self> {
+        let root = camera.config(context)?;
+
+        Ok(ConfigTransaction {
+            camera,
+            root,
+            staged: Vec::new(),
+        })
+    }
+
+    /// Stages a change to a setting.
+    ///
+    /// The change is not written to the camera until the transaction is committed. Staging a
+    /// setting again replaces the previously staged value. The setting is identified by its name
+    /// or, if `name` starts with `/`, by its path (see `Widget::child_by_path()`).
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the change could not be staged:
+    ///
+    /// * `InvalidInput` if the camera does not have a setting with the given name or path, the
+    ///   setting can not be changed, or the value is not valid for the setting's widget (see
+    ///   `Widget::set_value()`).
+    pub fn set(&mut self, name: &str, value: SettingValue) -> ::Result<()> {
+        let widget = if name.starts_with('/') {
+            self.root.child_by_path(name)
+        }
+        else {
+            self.root.child_by_name(name)
+        };
+
+        let mut widget = match widget {
+            Some(widget) => widget,
+            None => return Err(::error::invalid_setting(name, None)),
+        };
+
+        if widget.readonly() {
+            return Err(::error::invalid_setting(name, None));
+        }
+
+        let previous = match widget.value() {
+            Some(previous) => previous,
+            None => return Err(::error::invalid_setting(name, None)),
+        };
+
+        widget.set_value(value)?;
+
+        if !self.staged.iter().any(|(staged, _)| staged.id() == widget.id()) {
+            self.staged.push((widget, previous));
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if no changes have been staged.
+    pub fn is_empty(&self) -> bool {
+        self.staged.is_empty()
+    }
+
+    /// Writes the staged changes to the camera.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns the error reported by the camera if the changes could not be written.
+    /// In that case, the previous values of the staged settings are written to the camera before the
+    /// error is returned.
+    pub fn commit(mut self, context: &mut Context) -> ::Result<()> {
+        if self.staged.is_empty() {
+            return Ok(());
+        }
+
+        let err = match self.camera.set_config(context, &mut self.root) {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+
+        // Some settings may have been written before the camera rejected another one. Restoring is
+        // best effort; the original error is more useful to the caller than a failed rollback.
+        for (widget, previous) in &mut self.staged {
+            let _ = widget.set_value(previous.clone());
+        }
+
+        let _ = self.camera.set_config(context, &mut self.root);
+
+        Err(err)
+    }
+}
@@ -0,0 +1,225 @@
+use std::cmp;
+use std::thread;
+use std::time::{Duration,Instant};
+
+use ::camera::{Camera,CameraEvent};
+use ::context::Context;
+use ::error::ErrorKind;
+use ::snapshot::ConfigSnapshot;
+use ::widget::SettingValue;
+
+/// Default time between re-reads of the configuration when the camera does not report changes.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A notification that a setting's value changed.
+#[derive(Debug,PartialEq,Clone)]
+pub struct SettingChanged {
+    /// The setting's name.
+    pub name: String,
+
+    /// The previous value.
+    pub old: SettingValue,
+
+    /// The new value.
+    pub new: SettingValue,
+}
+
+/// Watches a camera's settings for changes, e.g., made with the camera's dials.
+///
+/// Cameras that support events report property changes through `Camera::wait_for_event()`. The
+/// watcher re-reads the changed settings when such an event is handled. For cameras that do not
+/// report property changes, the watcher periodically re-reads the complete configuration instead,
+/// until it sees the first property change event. From then on, the watcher relies on events and
+/// no longer re-reads the configuration on its own; call `poll()` to re-read it explicitly. If the
+/// camera's driver does not support events at all, `wait()` sleeps between re-reads instead of
+/// reading events.
+///
+/// The watcher compares the values it reads with the last known values, so each change is reported
+/// once. Changes of date settings, such as the camera's clock, are not reported.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// let mut context = gphoto::Context::new().unwrap();
+/// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+///
+/// let mut watcher = gphoto::ConfigWatcher::new(&mut camera, &mut context).unwrap();
+///
+/// loop {
+///     for change in watcher.wait(&mut camera, &mut context, Duration::from_secs(1)).unwrap() {
+///         println!("{}: {:?} -> {:?}", change.name, change.old, change.new);
+///     }
+/// }
+/// ```
+pub struct ConfigWatcher {
+    known: ConfigSnapshot,
+    poll_interval: Duration,
+    last_poll: Instant,
+    events_seen: bool,
+    events_unsupported: bool,
+}
+
+impl ConfigWatcher {
+    /// Starts watching a camera by reading its current configuration.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera's configuration could not be retrieved:
+    ///
+    /// * `NotSupported` if the camera can not be configured.
+    pub fn new(camera: &mut Camera, context: &mut Context) -> ::Result<Self> {
+        Ok(ConfigWatcher {
+            known: ConfigSnapshot::from_widget(&camera.config(context)?),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            last_poll: Instant::now(),
+            events_seen: false,
+            events_unsupported: false,
+        })
+    }
+
+    /// Sets the time between re-reads of the configuration for cameras that do not report changes.
+    ///
+    /// The default interval is two seconds.
+    pub fn set_poll_interval(&mut self, interval: Duration) {
+        self.poll_interval = interval;
+    }
+
+    /// Waits up to `timeout` for changes.
+    ///
+    /// Events are read from the camera until `timeout` has passed, and property change events are
+    /// handled with `handle_event()`. Until the camera reports a property change, the configuration
+    /// is also re-read with `poll()` whenever the poll interval has passed, even while waiting for
+    /// events. If the camera does not support events, the watcher sleeps between polls instead.
+    ///
+    /// Other events, such as `CameraEvent::FileAdded`, are discarded. Read events with
+    /// `Camera::wait_for_event()` and pass them to `handle_event()` to handle them as well.
+    pub fn wait(&mut self, camera: &mut Camera, context: &mut Context, timeout: Duration) -> ::Result<Vec<SettingChanged>> {
+        let deadline = Instant::now() + timeout;
+        let mut changes = Vec::new();
+
+        loop {
+            let polling = !self.events_seen;
+
+            if polling && self.last_poll.elapsed() >= self.poll_interval {
+                changes.extend(self.poll(camera, context)?);
+            }
+
+            let now = Instant::now();
+
+            if now >= deadline {
+                break;
+            }
+
+            // Cameras that support events may never report property changes, so waiting for events
+            // must not delay the next poll.
+            let until = if polling { cmp::min(deadline, self.last_poll + self.poll_interval) } else { deadline };
+
+            if self.events_unsupported {
+                thread::sleep(until.saturating_duration_since(now));
+                continue;
+            }
+
+            match camera.wait_for_event(context, until.saturating_duration_since(now)) {
+                Ok(CameraEvent::Timeout) => (),
+                Ok(event) => changes.extend(self.handle_event(camera, context, &event)?),
+                Err(ref err) if err.kind() == ErrorKind::NotSupported => self.events_unsupported = true,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Handles an event read from the camera.
+    ///
+    /// Returns the changes reported by a property change event. Events that do not report property
+    /// changes are ignored.
+    pub fn handle_event(&mut self, camera: &mut Camera, context: &mut Context, event: &CameraEvent) -> ::Result<Vec<SettingChanged>> {
+        let text = match *event {
+            CameraEvent::Unknown(ref text) => text,
+            _ => return Ok(Vec::new()),
+        };
+
+        let name = match util::changed_property(text) {
+            Some(name) => name,
+            None => return Ok(Vec::new()),
+        };
+
+        self.events_seen = true;
+
+        // Newer drivers name the widget of the changed property; otherwise every setting may have
+        // changed.
+        let name = match name {
+            Some(name) => name,
+            None => return self.poll(camera, context),
+        };
+
+        let value = match camera.config_widget(context, &name).ok().and_then(|widget| widget.value()) {
+            Some(value) => value,
+            None => return Ok(Vec::new()),
+        };
+
+        let change = self.update(&name, value);
+
+        Ok(change.into_iter().collect())
+    }
+
+    /// Re-reads the complete configuration and returns the changed settings.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the camera's configuration could not be retrieved.
+    pub fn poll(&mut self, camera: &mut Camera, context: &mut Context) -> ::Result<Vec<SettingChanged>> {
+        let current = ConfigSnapshot::from_widget(&camera.config(context)?);
+
+        self.last_poll = Instant::now();
+
+        let changes = self.known.diff(&current).changed.into_iter()
+            .filter(|(_, change)| !util::is_date(&change.new))
+            .map(|(name, change)| SettingChanged { name, old: change.old, new: change.new })
+            .collect();
+
+        self.known = current;
+
+        Ok(changes)
+    }
+
+    fn update(&mut self, name: &str, value: SettingValue) -> Option<SettingChanged> {
+        let old = self.known.settings.insert(name.to_owned(), value.clone())?;
+
+        if old == value || util::is_date(&value) {
+            return None;
+        }
+
+        Some(SettingChanged {
+            name: name.to_owned(),
+            old,
+            new: value,
+        })
+    }
+}
+
+mod util {
+    use ::widget::SettingValue;
+
+    /// Parses the description of a property change event.
+    ///
+    /// Returns `None` if the event is not a property change. Otherwise, returns the name of the
+    /// changed widget if the description contains it, e.g., `PTP Property 5007 changed, "f-number"
+    /// to "f/5.6"`.
+    pub fn changed_property(text: &str) -> Option<Option<String>> {
+        if !text.starts_with("PTP Property") || !text.contains("changed") {
+            return None;
+        }
+
+        let mut quoted = text.split('"').skip(1);
+
+        Some(quoted.next().filter(|name| !name.is_empty()).map(|name| name.to_owned()))
+    }
+
+    pub fn is_date(value: &SettingValue) -> bool {
+        matches!(*value, SettingValue::Date(_))
+    }
+}
@@ -0,0 +1,911 @@
+use std::borrow::Cow;
+use std::ffi::CString;
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::rc::Rc;
+use std::time::{Duration,Instant};
+
+use ::libc::{c_char,c_float,c_int,c_void};
+
+use ::camera::{Camera,CameraEvent};
+use ::context::Context;
+use ::error::ErrorKind;
+use ::handle::{Handle,HandleMut};
+
+/// Time without events after which a pressed widget's action is considered complete.
+const PRESS_IDLE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Maximum time to wait for a pressed widget's action to complete.
+const PRESS_MAX_WAIT: Duration = Duration::from_secs(10);
+
+/// Types of configuration widgets.
+#[derive(Debug,PartialEq,Eq,Clone,Copy,Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize,Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum WidgetType {
+    /// The top-level window of a configuration tree.
+    Window,
+
+    /// A section that groups related widgets.
+    Section,
+
+    /// A text value.
+    Text,
+
+    /// A numeric value within a range.
+    Range,
+
+    /// An on/off value.
+    Toggle,
+
+    /// A choice from a list of options, typically displayed as radio buttons.
+    Radio,
+
+    /// A choice from a list of options, typically displayed as a drop-down menu.
+    Menu,
+
+    /// A button that triggers an action.
+    Button,
+
+    /// A date and time.
+    Date,
+}
+
+/// The value of a configuration setting.
+#[derive(Debug,PartialEq,Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize,Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum SettingValue {
+    /// The value of a `Text` widget.
+    Text(String),
+
+    /// The value of a `Range` widget.
+    Range(f32),
+
+    /// The value of a `Toggle` widget.
+    Toggle(bool),
+
+    /// The selected choice of a `Radio` or `Menu` widget.
+    Choice(String),
+
+    /// The value of a `Date` widget in seconds since the Unix epoch.
+    Date(i64),
+}
+
+/// The bounds of a `Range` widget.
+#[derive(Debug,PartialEq,Clone,Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize,Deserialize))]
+pub struct WidgetRange {
+    /// The minimum value.
+    pub min: f32,
+
+    /// The maximum value.
+    pub max: f32,
+
+    /// The increment between valid values.
+    pub step: f32,
+}
+
+/// Owns a tree of widgets returned by `libgphoto2`.
+///
+/// Child widgets are freed together with the root of their tree, so every `Widget` keeps its tree
+/// alive.
+struct Tree {
+    root: *mut ::gphoto2::CameraWidget,
+}
+
+impl Drop for Tree {
+    fn drop(&mut self) {
+        unsafe {
+            ::gphoto2::gp_widget_unref(self.root);
+        }
+    }
+}
+
+/// A widget in a camera's configuration tree.
+///
+/// A camera's configuration is a tree of widgets. The root of the tree is a `Window`, which
+/// contains `Section` widgets that group the widgets for individual settings. Each setting widget
+/// has a name, which identifies the setting, a human-readable label, and a value.
+///
+/// ## Example
+///
+/// ```no_run
+/// fn print_widget(widget: &gphoto::Widget, depth: usize) {
+///     println!("{:indent$}{} ({:?}) = {:?}",
+///              "", widget.name(), widget.widget_type(), widget.value(), indent = depth * 2);
+///
+///     for child in widget.children() {
+///         print_widget(&child, depth + 1);
+///     }
+/// }
+///
+/// let mut context = gphoto::Context::new().unwrap();
+/// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+///
+/// print_widget(&camera.config(&mut context).unwrap(), 0);
+/// ```
+///
+/// The above example may print something like the following:
+///
+/// ```text
+/// main (Window) = None
+///   actions (Section) = None
+///     autofocusdrive (Toggle) = Some(Toggle(false))
+///   settings (Section) = None
+///     datetime (Date) = Some(Date(1446314700))
+///   imgsettings (Section) = None
+///     iso (Radio) = Some(Choice("100"))
+/// ```
+#[derive(Clone)]
+pub struct Widget {
+    tree: Rc<Tree>,
+    widget: *mut ::gphoto2::CameraWidget,
+}
+
+impl Widget {
+    /// Returns the widget's name.
+    ///
+    /// The name identifies the setting, e.g., `"iso"`.
+    pub fn name(&self) -> Cow<'_, str> {
+        let mut name = mem::MaybeUninit::uninit();
+
+        unsafe {
+            assert_eq!(::gphoto2::GP_OK, ::gphoto2::gp_widget_get_name(self.widget, name.as_mut_ptr()));
+            util::string_from_ptr(name.assume_init())
+        }
+    }
+
+    /// Returns the widget's human-readable label, e.g., `"ISO Speed"`.
+    pub fn label(&self) -> Cow<'_, str> {
+        let mut label = mem::MaybeUninit::uninit();
+
+        unsafe {
+            assert_eq!(::gphoto2::GP_OK, ::gphoto2::gp_widget_get_label(self.widget, label.as_mut_ptr()));
+            util::string_from_ptr(label.assume_init())
+        }
+    }
+
+    /// Returns additional information about the widget.
+    ///
+    /// The information is typically a help text describing the setting. It is often empty.
+    pub fn info(&self) -> Cow<'_, str> {
+        let mut info = mem::MaybeUninit::uninit();
+
+        unsafe {
+            assert_eq!(::gphoto2::GP_OK, ::gphoto2::gp_widget_get_info(self.widget, info.as_mut_ptr()));
+            util::string_from_ptr(info.assume_init())
+        }
+    }
+
+    /// Returns the widget's numeric identifier, which is unique within its tree.
+    pub fn id(&self) -> i32 {
+        let mut id = mem::MaybeUninit::uninit();
+
+        unsafe {
+            assert_eq!(::gphoto2::GP_OK, ::gphoto2::gp_widget_get_id(self.widget, id.as_mut_ptr()));
+            id.assume_init()
+        }
+    }
+
+    /// Returns the type of the widget.
+    pub fn widget_type(&self) -> WidgetType {
+        let mut widget_type = mem::MaybeUninit::uninit();
+
+        unsafe {
+            assert_eq!(::gphoto2::GP_OK, ::gphoto2::gp_widget_get_type(self.widget, widget_type.as_mut_ptr()));
+        }
+
+        match unsafe { widget_type.assume_init() } {
+            ::gphoto2::CameraWidgetType::GP_WIDGET_WINDOW  => WidgetType::Window,
+            ::gphoto2::CameraWidgetType::GP_WIDGET_SECTION => WidgetType::Section,
+            ::gphoto2::CameraWidgetType::GP_WIDGET_TEXT    => WidgetType::Text,
+            ::gphoto2::CameraWidgetType::GP_WIDGET_RANGE   => WidgetType::Range,
+            ::gphoto2::CameraWidgetType::GP_WIDGET_TOGGLE  => WidgetType::Toggle,
+            ::gphoto2::CameraWidgetType::GP_WIDGET_RADIO   => WidgetType::Radio,
+            ::gphoto2::CameraWidgetType::GP_WIDGET_MENU    => WidgetType::Menu,
+            ::gphoto2::CameraWidgetType::GP_WIDGET_BUTTON  => WidgetType::Button,
+            ::gphoto2::CameraWidgetType::GP_WIDGET_DATE    => WidgetType::Date,
+        }
+    }
+
+    /// Returns `true` if the setting can not be changed.
+    pub fn readonly(&self) -> bool {
+        let mut readonly = mem::MaybeUninit::uninit();
+
+        unsafe {
+            assert_eq!(::gphoto2::GP_OK, ::gphoto2::gp_widget_get_readonly(self.widget, readonly.as_mut_ptr()));
+            readonly.assume_init() != 0
+        }
+    }
+
+    /// Returns the widget's current value.
+    ///
+    /// Returns `None` for `Window`, `Section` and `Button` widgets, which do not have a value.
+    pub fn value(&self) -> Option<SettingValue> {
+        match self.widget_type() {
+            WidgetType::Text => Some(SettingValue::Text(self.string_value())),
+            WidgetType::Radio | WidgetType::Menu => Some(SettingValue::Choice(self.string_value())),
+            WidgetType::Range => {
+                let mut value: c_float = 0.0;
+                self.get_value(&mut value as *mut c_float as *mut c_void);
+                Some(SettingValue::Range(value as f32))
+            },
+            WidgetType::Toggle => {
+                let mut value: c_int = 0;
+                self.get_value(&mut value as *mut c_int as *mut c_void);
+                Some(SettingValue::Toggle(value != 0))
+            },
+            WidgetType::Date => {
+                let mut value: c_int = 0;
+                self.get_value(&mut value as *mut c_int as *mut c_void);
+                Some(SettingValue::Date(value as i64))
+            },
+            WidgetType::Window | WidgetType::Section | WidgetType::Button => None,
+        }
+    }
+
+    /// Sets the widget's value.
+    ///
+    /// The value is only changed in the widget. It is sent to the camera with
+    /// `Camera::set_setting()` or by writing the widget's configuration tree back to the camera.
+    ///
+    /// The value is checked against the widget before it is set. Values of `Range` widgets are
+    /// rounded to the nearest step within the range, and values of `Radio` and `Menu` widgets must be
+    /// one of the widget's choices.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use gphoto::{ErrorKind,SettingValue};
+    ///
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// let mut iso = camera.config_widget(&mut context, "iso").unwrap();
+    ///
+    /// if let Err(err) = iso.set_value(SettingValue::Choice("450".to_owned())) {
+    ///     assert_eq!(ErrorKind::InvalidInput, err.kind());
+    ///     println!("{}", err);
+    /// }
+    /// ```
+    ///
+    /// The above example may print something like the following:
+    ///
+    /// ```text
+    /// Bad parameters for setting "iso" (closest valid value: "400")
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the value could not be set:
+    ///
+    /// * `InvalidInput` if the type of the value does not match the type of the widget, e.g., a
+    ///   `SettingValue::Text` for a `Toggle` widget, a value is outside of the widget's range or not
+    ///   one of its choices, or a date is out of range. The error's `setting()` is the widget's name,
+    ///   and its `suggestion()` is the closest valid value, if there is one.
+    pub fn set_value(&mut self, value: SettingValue) -> ::Result<()> {
+        match (self.widget_type(), value) {
+            (WidgetType::Text, SettingValue::Text(text)) => self.set_string_value(text),
+            (WidgetType::Radio, SettingValue::Choice(choice))
+            | (WidgetType::Menu, SettingValue::Choice(choice)) => {
+                let choices = self.choices();
+
+                // Widgets without choices are left to the camera to validate.
+                if !choices.is_empty() && !choices.contains(&choice) {
+                    return Err(::error::invalid_setting(&self.name(), util::closest_choice(&choice, &choices)));
+                }
+
+                self.set_string_value(choice)
+            },
+            (WidgetType::Range, SettingValue::Range(value)) => {
+                let value = match self.range() {
+                    Some(range) => match util::snap_to_range(value, range) {
+                        Ok(value) => value,
+                        Err(closest) => return Err(::error::invalid_setting(&self.name(), Some(closest.to_string()))),
+                    },
+                    None => value,
+                };
+
+                let value = value as c_float;
+                self.set_raw_value(&value as *const c_float as *const c_void)
+            },
+            (WidgetType::Toggle, SettingValue::Toggle(value)) => {
+                let value = value as c_int;
+                self.set_raw_value(&value as *const c_int as *const c_void)
+            },
+            (WidgetType::Date, SettingValue::Date(value)) => {
+                if value < c_int::MIN as i64 || value > c_int::MAX as i64 {
+                    return Err(::error::invalid_setting(&self.name(), None));
+                }
+
+                let value = value as c_int;
+                self.set_raw_value(&value as *const c_int as *const c_void)
+            },
+            _ => Err(::error::invalid_setting(&self.name(), None)),
+        }
+    }
+
+    /// Presses a `Button`, `Toggle` or release `Radio` widget and waits for the action to complete.
+    ///
+    /// Pressing a `Button` runs its action on the camera. Pressing a `Toggle` writes the opposite of
+    /// its current value to the camera: momentary toggles such as `autofocusdrive` read as off, so
+    /// pressing them triggers their action, and toggles such as `viewfinder` are switched on or off.
+    ///
+    /// Release widgets such as Canon's `eosremoterelease` are `Radio` widgets with pairs of choices
+    /// such as `"Press Full"` and `"Release Full"`. Pressing them writes the press choice and then
+    /// the matching release choice. A full press is preferred if the widget offers several pairs.
+    ///
+    /// The widget must belong to `camera`'s configuration. If it was found in a complete
+    /// configuration tree, e.g., with `child_by_path()`, the whole tree is written to the camera.
+    /// After the action is started, the camera's events are collected until the camera is idle, and
+    /// the collected events are returned. After a release widget is pressed, events are collected at
+    /// least until the camera reports `FileAdded` or `CaptureComplete`, so that the captured file is
+    /// not missed while the exposure is made and written. Events are collected for at most ten
+    /// seconds. If the camera does not support events, no events are returned.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns an error if the widget could not be pressed:
+    ///
+    /// * `InvalidInput` if the widget is neither a button, a toggle nor a radio widget with press and
+    ///   release choices, or the camera rejected the action.
+    /// * `NotSupported` if a button does not have an action.
+    pub fn press(&mut self, camera: &mut Camera, context: &mut Context) -> ::Result<Vec<CameraEvent>> {
+        let released = match self.widget_type() {
+            WidgetType::Button => {
+                let mut callback: Option<::gphoto2::CameraWidgetCallback> = None;
+                self.get_value(&mut callback as *mut Option<::gphoto2::CameraWidgetCallback> as *mut c_void);
+
+                let callback = match callback {
+                    Some(callback) => callback,
+                    None => return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_NOT_SUPPORTED)),
+                };
+
+                try_unsafe!(callback(camera.as_mut_ptr(), self.widget, context.as_mut_ptr()));
+
+                false
+            },
+            WidgetType::Toggle => {
+                let on = self.value() == Some(SettingValue::Toggle(true));
+
+                self.set_value(SettingValue::Toggle(!on))?;
+                self.write(camera, context)?;
+
+                false
+            },
+            WidgetType::Radio => {
+                let (press, release) = match util::release_choices(&self.choices()) {
+                    Some(choices) => choices,
+                    None => return Err(::error::invalid_setting(&self.name(), None)),
+                };
+
+                self.set_value(SettingValue::Choice(press))?;
+                self.write(camera, context)?;
+
+                self.set_value(SettingValue::Choice(release))?;
+                self.write(camera, context)?;
+
+                true
+            },
+            _ => return Err(::error::invalid_setting(&self.name(), None)),
+        };
+
+        let deadline = Instant::now() + PRESS_MAX_WAIT;
+        let mut capture_pending = released;
+        let mut events = Vec::new();
+
+        loop {
+            let now = Instant::now();
+
+            if now >= deadline {
+                break;
+            }
+
+            // The camera may be quiet for longer than the idle timeout while it exposes and writes
+            // an image.
+            let timeout = if capture_pending { deadline - now } else { PRESS_IDLE_TIMEOUT };
+
+            // The action has already been performed, so a camera without events is not an error.
+            match camera.wait_for_event(context, timeout) {
+                Ok(CameraEvent::Timeout) => break,
+                Ok(event) => {
+                    if let CameraEvent::FileAdded(_) | CameraEvent::CaptureComplete = event {
+                        capture_pending = false;
+                    }
+
+                    events.push(event);
+                },
+                Err(ref err) if err.kind() == ErrorKind::NotSupported => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Returns the choices of a `Radio` or `Menu` widget.
+    ///
+    /// Returns an empty `Vec` for other types of widgets.
+    pub fn choices(&self) -> Vec<String> {
+        match self.widget_type() {
+            WidgetType::Radio | WidgetType::Menu => (),
+            _ => return Vec::new(),
+        }
+
+        let count = unsafe { ::gphoto2::gp_widget_count_choices(self.widget) };
+
+        (0..count).filter_map(|i| {
+            let mut choice = mem::MaybeUninit::uninit();
+
+            match unsafe { ::gphoto2::gp_widget_get_choice(self.widget, i, choice.as_mut_ptr()) } {
+                ::gphoto2::GP_OK => Some(unsafe { util::string_from_ptr(choice.assume_init()) }.into_owned()),
+                _ => None,
+            }
+        }).collect()
+    }
+
+    /// Returns the bounds of a `Range` widget.
+    ///
+    /// Returns `None` for other types of widgets.
+    pub fn range(&self) -> Option<WidgetRange> {
+        if self.widget_type() != WidgetType::Range {
+            return None;
+        }
+
+        let mut min: c_float = 0.0;
+        let mut max: c_float = 0.0;
+        let mut step: c_float = 0.0;
+
+        match unsafe { ::gphoto2::gp_widget_get_range(self.widget, &mut min, &mut max, &mut step) } {
+            ::gphoto2::GP_OK => Some(WidgetRange { min: min as f32, max: max as f32, step: step as f32 }),
+            _ => None,
+        }
+    }
+
+    /// Returns the widget's children.
+    pub fn children(&self) -> Vec<Widget> {
+        let count = unsafe { ::gphoto2::gp_widget_count_children(self.widget) };
+
+        (0..count).filter_map(|i| {
+            let mut child = mem::MaybeUninit::uninit();
+
+            match unsafe { ::gphoto2::gp_widget_get_child(self.widget, i, child.as_mut_ptr()) } {
+                ::gphoto2::GP_OK => Some(self.with_ptr(unsafe { child.assume_init() })),
+                _ => None,
+            }
+        }).collect()
+    }
+
+    /// Finds a descendant widget by name.
+    ///
+    /// All descendants of the widget are searched, not only its direct children. Returns `None` if
+    /// no descendant has the given name.
+    pub fn child_by_name(&self, name: &str) -> Option<Widget> {
+        let name = match CString::new(name) {
+            Ok(s) => s,
+            Err(_) => return None,
+        };
+
+        let mut child = mem::MaybeUninit::uninit();
+
+        match unsafe { ::gphoto2::gp_widget_get_child_by_name(self.widget, name.as_ptr(), child.as_mut_ptr()) } {
+            ::gphoto2::GP_OK => Some(self.with_ptr(unsafe { child.assume_init() })),
+            _ => None,
+        }
+    }
+
+    /// Returns the widget's path in its tree.
+    ///
+    /// The path consists of the names of the widget and its ancestors separated by `/`, starting
+    /// with the name of the tree's root, e.g., `"/main/imgsettings/iso"`. A widget retrieved with
+    /// `Camera::config_widget()` is the root of its own tree, so its path is only its name.
+    pub fn path(&self) -> String {
+        let mut names = Vec::new();
+        let mut widget = self.clone();
+
+        loop {
+            names.push(widget.name().into_owned());
+
+            let mut parent = mem::MaybeUninit::uninit();
+
+            match unsafe { ::gphoto2::gp_widget_get_parent(widget.widget, parent.as_mut_ptr()) } {
+                ::gphoto2::GP_OK if !unsafe { parent.assume_init() }.is_null() => {
+                    widget = self.with_ptr(unsafe { parent.assume_init() });
+                },
+                _ => break,
+            }
+        }
+
+        names.iter().rev().fold(String::new(), |path, name| path + "/" + name)
+    }
+
+    /// Finds a widget in the widget's tree by path.
+    ///
+    /// The path is resolved from the root of the tree, regardless of which widget of the tree this
+    /// is called on. Returns `None` if the tree does not contain a widget with the given path.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// let config = camera.config(&mut context).unwrap();
+    /// let iso = config.child_by_path("/main/imgsettings/iso").unwrap();
+    ///
+    /// println!("{} = {:?}", iso.label(), iso.value());
+    /// ```
+    pub fn child_by_path(&self, path: &str) -> Option<Widget> {
+        let mut names = path.split('/').filter(|name| !name.is_empty());
+        let mut widget = self.with_ptr(self.tree.root);
+
+        if names.next() != Some(&*widget.name()) {
+            return None;
+        }
+
+        for name in names {
+            widget = widget.children().into_iter().find(|child| child.name() == name)?;
+        }
+
+        Some(widget)
+    }
+
+    /// Searches the widget and its descendants, returning the paths of the matching widgets.
+    ///
+    /// A widget matches if its name is equal to `pattern`, its label contains `pattern` ignoring
+    /// case, or `pattern` is a glob that matches its name or path. Globs may contain `*`, which
+    /// matches any sequence of characters, and `?`, which matches a single character. An empty
+    /// pattern matches no widgets.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let mut context = gphoto::Context::new().unwrap();
+    /// let mut camera = gphoto::Camera::autodetect(&mut context).unwrap();
+    ///
+    /// let config = camera.config(&mut context).unwrap();
+    ///
+    /// for path in config.search("*focus*") {
+    ///     println!("{}", path);
+    /// }
+    /// ```
+    pub fn search(&self, pattern: &str) -> Vec<String> {
+        let mut paths = Vec::new();
+
+        if pattern.is_empty() {
+            return paths;
+        }
+
+        self.search_into(pattern, &self.path(), &mut paths);
+
+        paths
+    }
+
+    fn search_into(&self, pattern: &str, path: &str, paths: &mut Vec<String>) {
+        let name = self.name();
+        let is_glob = pattern.contains('*') || pattern.contains('?');
+
+        let matches = name == pattern
+            || self.label().to_lowercase().contains(&pattern.to_lowercase())
+            || (is_glob && (util::glob_match(pattern, &name) || util::glob_match(pattern, path)));
+
+        if matches {
+            paths.push(path.to_owned());
+        }
+
+        for child in self.children() {
+            child.search_into(pattern, &format!("{}/{}", path, child.name()), paths);
+        }
+    }
+
+    /// Writes the widget's value to the camera.
+    ///
+    /// A widget that is part of a complete configuration tree is written together with its tree,
+    /// because `Camera::set_config_widget()` looks the widget up by name and could write a different
+    /// widget with the same name.
+    fn write(&mut self, camera: &mut Camera, context: &mut Context) -> ::Result<()> {
+        if self.widget == self.tree.root {
+            camera.set_config_widget(context, self)
+        }
+        else {
+            camera.set_config(context, &mut self.with_ptr(self.tree.root))
+        }
+    }
+
+    fn with_ptr(&self, widget: *mut ::gphoto2::CameraWidget) -> Widget {
+        Widget {
+            tree: self.tree.clone(),
+            widget,
+        }
+    }
+
+    fn get_value(&self, value: *mut c_void) {
+        unsafe {
+            assert_eq!(::gphoto2::GP_OK, ::gphoto2::gp_widget_get_value(self.widget, value));
+        }
+    }
+
+    fn set_raw_value(&mut self, value: *const c_void) -> ::Result<()> {
+        try_unsafe!(::gphoto2::gp_widget_set_value(self.widget, value));
+
+        Ok(())
+    }
+
+    fn set_string_value(&mut self, value: String) -> ::Result<()> {
+        let value = match CString::new(value) {
+            Ok(s) => s,
+            Err(_) => return Err(::error::invalid_setting(&self.name(), None)),
+        };
+
+        self.set_raw_value(value.as_ptr() as *const c_void)
+    }
+
+    fn string_value(&self) -> String {
+        let mut value: *const c_char = ptr::null();
+        self.get_value(&mut value as *mut *const c_char as *mut c_void);
+
+        if value.is_null() {
+            String::new()
+        }
+        else {
+            unsafe { util::string_from_ptr(value) }.into_owned()
+        }
+    }
+}
+
+impl fmt::Debug for Widget {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Widget")
+            .field("name", &self.name())
+            .field("label", &self.label())
+            .field("widget_type", &self.widget_type())
+            .field("value", &self.value())
+            .finish()
+    }
+}
+
+#[doc(hidden)]
+impl Handle<::gphoto2::CameraWidget> for Widget {
+    unsafe fn as_ptr(&self) -> *const ::gphoto2::CameraWidget {
+        self.widget
+    }
+}
+
+#[doc(hidden)]
+impl HandleMut<::gphoto2::CameraWidget> for Widget {
+    unsafe fn as_mut_ptr(&mut self) -> *mut ::gphoto2::CameraWidget {
+        self.widget
+    }
+}
+
+/// Takes ownership of a widget tree returned by `libgphoto2`.
+#[doc(hidden)]
+pub fn from_libgphoto2(root: *mut ::gphoto2::CameraWidget) -> Widget {
+    Widget {
+        tree: Rc::new(Tree { root }),
+        widget: root,
+    }
+}
+
+mod util {
+    use std::borrow::Cow;
+    use std::cmp::Ordering;
+    use std::ffi::CStr;
+
+    use ::libc::c_char;
+
+    use super::WidgetRange;
+
+    pub unsafe fn string_from_ptr<'a>(ptr: *const c_char) -> Cow<'a, str> {
+        String::from_utf8_lossy(CStr::from_ptr(ptr).to_bytes())
+    }
+
+    /// Matches text against a glob in which `*` matches any sequence of characters and `?` matches
+    /// a single character.
+    pub fn glob_match(pattern: &str, text: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let text: Vec<char> = text.chars().collect();
+
+        let (mut p, mut t) = (0, 0);
+
+        // Position of the last `*` and the text position it was tried at, for backtracking.
+        let mut star: Option<(usize, usize)> = None;
+
+        while t < text.len() {
+            if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+                p += 1;
+                t += 1;
+            }
+            else if p < pattern.len() && pattern[p] == '*' {
+                star = Some((p, t));
+                p += 1;
+            }
+            else if let Some((star_p, star_t)) = star {
+                p = star_p + 1;
+                t = star_t + 1;
+                star = Some((star_p, star_t + 1));
+            }
+            else {
+                return false;
+            }
+        }
+
+        pattern[p..].iter().all(|&c| c == '*')
+    }
+
+    /// Finds a pair of choices that press and release a shutter button, e.g., `"Press Full"` and
+    /// `"Release Full"`.
+    ///
+    /// A full press is preferred; otherwise, the first press choice with a matching release choice is
+    /// used.
+    pub fn release_choices(choices: &[String]) -> Option<(String, String)> {
+        let pair = |button: &str| {
+            let press = format!("Press {}", button);
+            let release = format!("Release {}", button);
+
+            if choices.contains(&press) && choices.contains(&release) {
+                Some((press, release))
+            }
+            else {
+                None
+            }
+        };
+
+        pair("Full").or_else(|| choices.iter().filter_map(|choice| choice.strip_prefix("Press ")).find_map(pair))
+    }
+
+    /// Rounds a value to the nearest step of a range.
+    ///
+    /// Returns the closest bound of the range as the error if the value is outside of the range.
+    pub fn snap_to_range(value: f32, range: WidgetRange) -> Result<f32, f32> {
+        // Allow for rounding errors in values that were computed from the range.
+        let tolerance = if range.step > 0.0 { range.step / 1000.0 } else { 0.0 };
+
+        if !value.is_finite() || value < range.min - tolerance || value > range.max + tolerance {
+            return Err(if value > range.max { range.max } else { range.min });
+        }
+
+        if range.step <= 0.0 {
+            return Ok(value);
+        }
+
+        let steps = ((value - range.min) / range.step).round();
+
+        Ok((range.min + steps * range.step).min(range.max).max(range.min))
+    }
+
+    /// Finds the choice that is most similar to a value.
+    ///
+    /// A choice that differs only in case is preferred. Numeric values, including fractions such as
+    /// `"1/250"` and f-numbers such as `"f/5.6"`, are compared by ratio; other values are compared by
+    /// edit distance.
+    pub fn closest_choice(value: &str, choices: &[String]) -> Option<String> {
+        if let Some(choice) = choices.iter().find(|choice| choice.eq_ignore_ascii_case(value)) {
+            return Some(choice.clone());
+        }
+
+        if let Some(number) = parse_number(value) {
+            let closest = choices.iter()
+                .filter_map(|choice| parse_number(choice).map(|n| (choice, (n / number).ln().abs())))
+                .min_by(|&(_, a), &(_, b)| a.partial_cmp(&b).unwrap_or(Ordering::Equal));
+
+            if let Some((choice, _)) = closest {
+                return Some(choice.clone());
+            }
+        }
+
+        choices.iter()
+            .min_by_key(|choice| edit_distance(&value.to_lowercase(), &choice.to_lowercase()))
+            .cloned()
+    }
+
+    /// Parses a positive number such as `"400"`, `"5.6"`, `"f/5.6"` or `"1/250"`.
+    fn parse_number(s: &str) -> Option<f64> {
+        let s = s.trim();
+        let s = s.strip_prefix("f/").unwrap_or(s);
+
+        let number = match s.find('/') {
+            Some(i) => s[..i].parse::<f64>().ok()? / s[i + 1..].parse::<f64>().ok()?,
+            None => s.parse::<f64>().ok()?,
+        };
+
+        if number.is_finite() && number > 0.0 { Some(number) } else { None }
+    }
+
+    /// Computes the Levenshtein distance between two strings.
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut row: Vec<usize> = (0..b.len() + 1).collect();
+
+        for (i, ca) in a.chars().enumerate() {
+            let mut diagonal = row[0];
+            row[0] = i + 1;
+
+            for (j, &cb) in b.iter().enumerate() {
+                let substitution = diagonal + if ca == cb { 0 } else { 1 };
+                diagonal = row[j + 1];
+                row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+            }
+        }
+
+        row[b.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WidgetRange;
+    use super::util::{glob_match,snap_to_range,closest_choice};
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn snap_to_range_rounds_to_nearest_step() {
+        let range = WidgetRange { min: -3.0, max: 3.0, step: 0.5 };
+
+        assert_eq!(Ok(1.5), snap_to_range(1.4, range));
+        assert_eq!(Ok(-2.0), snap_to_range(-2.2, range));
+        assert_eq!(Ok(3.0), snap_to_range(3.0, range));
+    }
+
+    #[test]
+    fn snap_to_range_rejects_values_outside_range() {
+        let range = WidgetRange { min: -3.0, max: 3.0, step: 0.5 };
+
+        assert_eq!(Err(3.0), snap_to_range(4.0, range));
+        assert_eq!(Err(-3.0), snap_to_range(-7.5, range));
+        assert_eq!(Err(-3.0), snap_to_range(f32::NAN, range));
+    }
+
+    #[test]
+    fn closest_choice_compares_numbers_by_ratio() {
+        let isos = strings(&["100", "200", "400", "800"]);
+        let apertures = strings(&["4", "5.6", "8"]);
+        let speeds = strings(&["1/125", "1/250", "1/500"]);
+
+        assert_eq!(Some("400".to_owned()), closest_choice("450", &isos));
+        assert_eq!(Some("5.6".to_owned()), closest_choice("f/5.6", &apertures));
+        assert_eq!(Some("1/250".to_owned()), closest_choice("1/200", &speeds));
+    }
+
+    #[test]
+    fn closest_choice_prefers_case_insensitive_match() {
+        assert_eq!(Some("Auto".to_owned()), closest_choice("auto", &strings(&["Auto", "Daylight"])));
+    }
+
+    #[test]
+    fn closest_choice_falls_back_to_edit_distance() {
+        let choices = strings(&["Auto", "Daylight", "Shade", "Tungsten"]);
+
+        assert_eq!(Some("Daylight".to_owned()), closest_choice("Dayligt", &choices));
+        assert_eq!(Some("Shade".to_owned()), closest_choice("shady", &choices));
+        assert_eq!(None, closest_choice("Auto", &[]));
+    }
+
+    #[test]
+    fn glob_match_matches_wildcards() {
+        assert!(glob_match("*focus*", "autofocusdrive"));
+        assert!(glob_match("*focus*", "focusmode"));
+        assert!(glob_match("is?", "iso"));
+        assert!(glob_match("eos*", "eosremoterelease"));
+        assert!(glob_match("eos*", "eos"));
+    }
+
+    #[test]
+    fn glob_match_matches_paths() {
+        assert!(glob_match("/main/*/iso", "/main/imgsettings/iso"));
+        assert!(!glob_match("/main/*/iso", "/main/imgsettings/isoauto"));
+    }
+
+    #[test]
+    fn glob_match_rejects_other_text() {
+        assert!(!glob_match("*focus*", "shutterspeed"));
+        assert!(!glob_match("is?", "is"));
+        assert!(!glob_match("is?", "isos"));
+        assert!(!glob_match("eos*", "canoneos"));
+    }
+}
@@ -0,0 +1,245 @@
+use std::path::{Path,PathBuf};
+use std::sync::mpsc::{self,Sender};
+use std::thread;
+
+use ::abilities::Abilities;
+use ::camera::{Camera,CameraEvent,CameraFile};
+use ::context::Context;
+use ::media::FileMedia;
+use ::widget::SettingValue;
+
+type Job = Box<dyn FnOnce(&mut Camera, &mut Context) + Send>;
+
+/// A handle to a camera that is owned by a dedicated worker thread.
+///
+/// `Camera` and `Context` can not be shared between threads. A `CameraHandle` opens the camera and
+/// its context on a worker thread and sends each operation to that thread as a request, waiting for
+/// the response. Handles can be cloned and sent to other threads; requests from all clones are
+/// performed one at a time in the order they are received.
+///
+/// The worker thread closes the camera and exits when every handle has been dropped.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::thread;
+///
+/// let camera = gphoto::CameraHandle::autodetect().unwrap();
+/// let worker = camera.clone();
+///
+/// let capture = thread::spawn(move || worker.capture_image()).join().unwrap().unwrap();
+/// let data = camera.download(&capture).unwrap();
+///
+/// println!("downloaded {} ({} bytes)", capture.basename(), data.len());
+/// ```
+#[derive(Clone)]
+pub struct CameraHandle {
+    jobs: Sender<Job>,
+}
+
+impl CameraHandle {
+    /// Starts a worker thread that opens a camera with the given function.
+    ///
+    /// The function is called on the worker thread with the worker's context.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let camera = gphoto::CameraHandle::spawn(|context| {
+    ///     gphoto::Camera::open(context, "Nikon DSC D750", "usb:001,007")
+    /// }).unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// This function returns the error returned by `open`, or an error if the worker's context
+    /// could not be created.
+    pub fn spawn<F>(open: F) -> ::Result<Self>
+        where F: FnOnce(&mut Context) -> ::Result<Camera> + Send + 'static
+    {
+        let (job_tx, job_rx) = mpsc::channel::<Job>();
+        let (ready_tx, ready_rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let opened = Context::new().and_then(|mut context| {
+                open(&mut context).map(|camera| (camera, context))
+            });
+
+            let (mut camera, mut context) = match opened {
+                Ok(opened) => {
+                    let _ = ready_tx.send(Ok(()));
+                    opened
+                },
+                Err(err) => {
+                    let _ = ready_tx.send(Err(err));
+                    return;
+                },
+            };
+
+            for job in job_rx {
+                job(&mut camera, &mut context);
+            }
+        });
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(CameraHandle { jobs: job_tx }),
+            Ok(Err(err)) => Err(err),
+            Err(_) => Err(worker_stopped()),
+        }
+    }
+
+    /// Starts a worker thread that opens the first detected camera.
+    pub fn autodetect() -> ::Result<Self> {
+        CameraHandle::spawn(Camera::autodetect)
+    }
+
+    /// Performs an operation on the worker thread and waits for its result.
+    ///
+    /// This can be used to perform any operation that does not have a dedicated method on
+    /// `CameraHandle`.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns the error returned by `operation`, or an error if the worker thread has
+    /// stopped.
+    pub fn call<T, F>(&self, operation: F) -> ::Result<T>
+        where T: Send + 'static,
+              F: FnOnce(&mut Camera, &mut Context) -> ::Result<T> + Send + 'static
+    {
+        let (result_tx, result_rx) = mpsc::channel();
+
+        let job: Job = Box::new(move |camera, context| {
+            let _ = result_tx.send(operation(camera, context));
+        });
+
+        if self.jobs.send(job).is_err() {
+            return Err(worker_stopped());
+        }
+
+        match result_rx.recv() {
+            Ok(result) => result,
+            Err(_) => Err(worker_stopped()),
+        }
+    }
+
+    /// Captures an image.
+    ///
+    /// See `Camera::capture_image()`.
+    pub fn capture_image(&self) -> ::Result<CameraFile> {
+        self.call(|camera, context| camera.capture_image(context))
+    }
+
+    /// Captures a preview image and returns its contents.
+    ///
+    /// See `Camera::capture_preview()`.
+    pub fn capture_preview(&self) -> ::Result<Vec<u8>> {
+        self.call(|camera, context| {
+            let mut preview = FileMedia::new()?;
+            camera.capture_preview(context, &mut preview)?;
+            preview.data().map(|data| data.to_vec())
+        })
+    }
+
+    /// Downloads a file from the camera and returns its contents.
+    ///
+    /// See `Camera::download()`.
+    pub fn download(&self, source: &CameraFile) -> ::Result<Vec<u8>> {
+        let source = source.clone();
+
+        self.call(move |camera, context| {
+            let mut file = FileMedia::new()?;
+            camera.download(context, &source, &mut file)?;
+            file.data().map(|data| data.to_vec())
+        })
+    }
+
+    /// Downloads a file from the camera to a new file at `destination`.
+    ///
+    /// See `Camera::download()` and `FileMedia::create()`.
+    pub fn download_to(&self, source: &CameraFile, destination: &Path) -> ::Result<()> {
+        let source = source.clone();
+        let destination: PathBuf = destination.to_owned();
+
+        self.call(move |camera, context| {
+            let mut file = FileMedia::create(&destination)?;
+            camera.download(context, &source, &mut file)
+        })
+    }
+
+    /// Lists the names of the folders in a folder on the camera's storage.
+    ///
+    /// See `Camera::list_folders()`.
+    pub fn list_folders(&self, folder: &str) -> ::Result<Vec<String>> {
+        let folder = folder.to_owned();
+
+        self.call(move |camera, context| camera.list_folders(context, &folder))
+    }
+
+    /// Lists the files in a folder on the camera's storage.
+    ///
+    /// See `Camera::list_files()`.
+    pub fn list_files(&self, folder: &str) -> ::Result<Vec<CameraFile>> {
+        let folder = folder.to_owned();
+
+        self.call(move |camera, context| camera.list_files(context, &folder))
+    }
+
+    /// Retrieves the camera's abilities.
+    ///
+    /// See `Camera::abilities()`.
+    pub fn abilities(&self) -> ::Result<Abilities> {
+        self.call(|camera, _| Ok(camera.abilities()))
+    }
+
+    /// Returns the value of a single setting.
+    ///
+    /// See `Camera::get_setting()`.
+    pub fn get_setting(&self, name: &str) -> ::Result<SettingValue> {
+        let name = name.to_owned();
+
+        self.call(move |camera, context| camera.get_setting(context, &name))
+    }
+
+    /// Changes the value of a single setting.
+    ///
+    /// See `Camera::set_setting()`.
+    pub fn set_setting(&self, name: &str, value: SettingValue) -> ::Result<()> {
+        let name = name.to_owned();
+
+        self.call(move |camera, context| camera.set_setting(context, &name, value))
+    }
+
+    /// Presses a button, toggle or release widget by name and waits for the action to complete.
+    ///
+    /// See `Camera::press()`.
+    pub fn press(&self, name: &str) -> ::Result<Vec<CameraEvent>> {
+        let name = name.to_owned();
+
+        self.call(move |camera, context| camera.press(context, &name))
+    }
+
+    /// Measures the difference between the camera's clock and the host's clock.
+    ///
+    /// See `Camera::clock_offset()`.
+    pub fn clock_offset(&self) -> ::Result<i64> {
+        self.call(|camera, context| camera.clock_offset(context))
+    }
+
+    /// Sets the camera's clock to the host's current time.
+    ///
+    /// See `Camera::sync_clock()`.
+    pub fn sync_clock(&self) -> ::Result<()> {
+        self.call(|camera, context| camera.sync_clock(context))
+    }
+
+    /// Returns the camera's summary.
+    ///
+    /// See `Camera::summary()`.
+    pub fn summary(&self) -> ::Result<String> {
+        self.call(|camera, context| camera.summary(context))
+    }
+}
+
+fn worker_stopped() -> ::Error {
+    ::error::from_libgphoto2(::gphoto2::GP_ERROR)
+}